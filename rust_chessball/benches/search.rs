@@ -0,0 +1,44 @@
+//! Criterion benchmarks for legacy move generation and search.
+//!
+//! Run from `rust_chessball/` with `cargo bench --bench search`; add a filter such as
+//! `cargo bench --bench search -- perft` to run a single group. Criterion keeps the
+//! previous results under `target/criterion` and reports changes against them.
+
+use std::hint::black_box;
+
+use chessball::board::{ChessBallBoard, Player};
+use chessball::minimax::choose_best_move;
+use chessball::moves::{perft, possible_moves};
+use criterion::{Criterion, criterion_group, criterion_main};
+
+fn bench_possible_moves(c: &mut Criterion) {
+    let board = ChessBallBoard::new_game();
+    c.bench_function("possible_moves/start", |b| {
+        b.iter(|| possible_moves(black_box(&board), Player::White))
+    });
+}
+
+fn bench_perft(c: &mut Criterion) {
+    let board = ChessBallBoard::new_game();
+    c.bench_function("perft/start/3", |b| {
+        b.iter(|| perft(black_box(&board), Player::White, 3))
+    });
+}
+
+fn bench_choose_best_move(c: &mut Criterion) {
+    let board = ChessBallBoard::new_game();
+    let mut group = c.benchmark_group("choose_best_move");
+    group.sample_size(10);
+    group.bench_function("start/3", |b| {
+        b.iter(|| choose_best_move(black_box(&board), Player::White, 3))
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_possible_moves,
+    bench_perft,
+    bench_choose_best_move
+);
+criterion_main!(benches);
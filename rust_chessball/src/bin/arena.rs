@@ -137,7 +137,8 @@ fn run() -> Result<(), String> {
         let path = config
             .out_dir
             .join(format!("game_{:04}.cbr", game_index + 1));
-        fs::write(&path, record.to_text())
+        record
+            .save(&path)
             .map_err(|err| format!("failed to write {}: {err}", path.display()))?;
     }
 
@@ -1,4 +1,4 @@
-use std::{env, fs, process};
+use std::{env, path::Path, process};
 
 use chessball::record::GameRecord;
 
@@ -23,9 +23,8 @@ fn run() -> Result<(), String> {
         }
     };
 
-    let content =
-        fs::read_to_string(&path).map_err(|err| format!("failed to read {path}: {err}"))?;
-    let record = GameRecord::from_text(&content)?;
+    let record = GameRecord::load(Path::new(&path))
+        .map_err(|err| format!("failed to read {path}: {err}"))?;
     let positions = record.replay_positions()?;
 
     println!("White: {}", record.white_label);
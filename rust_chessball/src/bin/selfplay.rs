@@ -49,7 +49,8 @@ fn run() -> Result<(), String> {
     let (report, records) = run_selfplay_tournament(config);
     for (idx, record) in records.iter().enumerate() {
         let path = out_dir.join(format!("game_{:04}.cbr", idx + 1));
-        fs::write(&path, record.to_text())
+        record
+            .save(&path)
             .map_err(|err| format!("failed to write {}: {err}", path.display()))?;
     }
 
@@ -5,10 +5,12 @@
 //! and parsing/printing the textual representation used in the original Python code.
 
 use std::fmt;
+use std::str::FromStr;
 
-use crate::moves::DefenderTackle;
+use crate::moves::{DefenderTackle, MoveInfo, apply_move};
+use crate::rng::Rng;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 /// Player owning a piece.
 pub enum Player {
     White,
@@ -33,6 +35,23 @@ impl Player {
         }
     }
 
+    /// Parse a side that can move from its initial, `"W"` or `"B"`. `"N"` (Neutral never
+    /// moves) and anything but a single initial give None.
+    ///
+    /// Example:
+    /// ```
+    /// use chessball::board::Player;
+    /// assert_eq!(Player::from_initial("B"), Some(Player::Black));
+    /// assert_eq!(Player::from_initial("N"), None);
+    /// ```
+    pub fn from_initial(s: &str) -> Option<Self> {
+        let mut chars = s.chars();
+        match (chars.next().and_then(Self::from_char), chars.next()) {
+            (Some(player @ (Player::White | Player::Black)), None) => Some(player),
+            _ => None,
+        }
+    }
+
     /// Convert Player to single-character player initial.
     ///
     /// Example:
@@ -47,9 +66,51 @@ impl Player {
             Player::Neutral => 'N',
         }
     }
+
+    /// The other side. `Neutral` (the ball's owner) has no opponent and maps to itself.
+    #[must_use]
+    pub const fn opponent(self) -> Self {
+        match self {
+            Player::White => Player::Black,
+            Player::Black => Player::White,
+            Player::Neutral => Player::Neutral,
+        }
+    }
+
+    /// Row the ball must reach for this player to win on a board with `rows` rows:
+    /// the last row for White, row 0 for Black and none for `Neutral`.
+    #[must_use]
+    pub const fn goal_row(self, rows: usize) -> Option<usize> {
+        match self {
+            Player::White => Some(rows - 1),
+            Player::Black => Some(0),
+            Player::Neutral => None,
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+impl FromStr for Player {
+    type Err = BoardError;
+
+    /// Parse a full name ("White", "Black", "Neutral") or an initial, ignoring case.
+    ///
+    /// Example:
+    /// ```
+    /// use chessball::board::Player;
+    /// assert_eq!("black".parse(), Ok(Player::Black));
+    /// assert_eq!("W".parse(), Ok(Player::White));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "white" | "w" => Ok(Player::White),
+            "black" | "b" => Ok(Player::Black),
+            "neutral" | "n" => Ok(Player::Neutral),
+            _ => Err(BoardError::UnknownPlayer(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 /// Type of piece.
 pub enum PieceType {
     Attacker,
@@ -90,7 +151,28 @@ impl PieceType {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+impl FromStr for PieceType {
+    type Err = BoardError;
+
+    /// Parse a full name ("Attacker", "Defender", "Ball") or an initial, ignoring case.
+    ///
+    /// Example:
+    /// ```
+    /// use chessball::board::PieceType;
+    /// assert_eq!("Defender".parse(), Ok(PieceType::Defender));
+    /// assert_eq!("b".parse(), Ok(PieceType::Ball));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "attacker" | "a" => Ok(PieceType::Attacker),
+            "defender" | "d" => Ok(PieceType::Defender),
+            "ball" | "b" => Ok(PieceType::Ball),
+            _ => Err(BoardError::UnknownPieceType(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 /// Board piece with a type and owner.
 pub struct Piece {
     pub piece_type: PieceType,
@@ -103,12 +185,55 @@ impl fmt::Display for Piece {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Coord {
     pub r: usize,
     pub c: usize,
 }
 
+impl Coord {
+    /// Algebraic name of this square on a board with `rows` rows: file letters, then the
+    /// rank, rank 1 being the bottom row (`rows - 1`). Files run `a`..`z`, then `aa`,
+    /// `ab`, ... like spreadsheet columns, so every column up to `MAX_COLS` has a name.
+    ///
+    /// Example:
+    /// ```
+    /// use chessball::board::Coord;
+    /// assert_eq!(Coord { r: 5, c: 0 }.algebraic(6), "a1");
+    /// assert_eq!(Coord { r: 0, c: 27 }.algebraic(6), "ab6");
+    /// ```
+    #[must_use]
+    pub fn algebraic(self, rows: usize) -> String {
+        let mut file = Vec::new();
+        let mut n = self.c + 1;
+        while n > 0 {
+            n -= 1;
+            file.push(b'a' + (n % 26) as u8);
+            n /= 26;
+        }
+        file.reverse();
+        format!("{}{}", String::from_utf8_lossy(&file), rows - self.r)
+    }
+
+    /// Parse a square named as by `algebraic` on a `rows` x `cols` board. File letters
+    /// are case-insensitive. Returns None for a malformed name or a square off the board.
+    #[must_use]
+    pub fn from_algebraic(name: &str, rows: usize, cols: usize) -> Option<Self> {
+        let name = name.trim();
+        let split = name.find(|ch: char| !ch.is_ascii_alphabetic())?;
+        let (file, rank) = name.split_at(split);
+        if file.is_empty() || !rank.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let c = file.bytes().try_fold(0usize, |acc, b| {
+            acc.checked_mul(26)?
+                .checked_add(usize::from(b.to_ascii_lowercase() - b'a') + 1)
+        })? - 1;
+        let rank: usize = rank.parse().ok()?;
+        ((1..=rows).contains(&rank) && c < cols).then(|| Self { r: rows - rank, c })
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct CoordDelta {
     pub r: isize,
@@ -149,7 +274,7 @@ impl std::ops::Sub<CoordDelta> for Coord {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 /// ChessBallBoard holds the board matrix. Defaults to 7 rows x 6 cols (same as Python port).
 pub struct ChessBallBoard {
     // row-major storage
@@ -158,7 +283,13 @@ pub struct ChessBallBoard {
     /// number of cols
     pub cols: usize,
     cells: Vec<Option<Piece>>,
+    /// Cached result of `scan_ball`, kept up to date by `place_piece`/`remove_piece`.
+    ball: Option<Coord>,
     pub prev_tackle: Option<DefenderTackle>,
+    /// Columns the ball may not be pushed into, one bit per column.
+    forbidden_cols: u64,
+    /// Directions each piece type may move in.
+    move_rules: MoveRules,
 }
 
 impl Default for ChessBallBoard {
@@ -170,6 +301,12 @@ impl Default for ChessBallBoard {
 impl ChessBallBoard {
     pub const DEFAULT_ROWS: usize = 6;
     pub const DEFAULT_COLS: usize = 7;
+    /// Widest supported board: one bit per column in the forbidden-column mask.
+    pub const MAX_COLS: usize = 64;
+    /// Maximum number of attackers per side accepted by [`ChessBallBoard::validate`].
+    pub const MAX_ATTACKERS: usize = TeamSpec::STANDARD.attackers;
+    /// Maximum number of defenders per side accepted by [`ChessBallBoard::validate`].
+    pub const MAX_DEFENDERS: usize = TeamSpec::STANDARD.defenders;
 
     /// Create an empty board with default dimensions.
     ///
@@ -183,62 +320,150 @@ impl ChessBallBoard {
     /// ```
     #[must_use]
     pub fn new() -> Self {
-        let rows = Self::DEFAULT_ROWS;
-        let cols = Self::DEFAULT_COLS;
-        Self {
+        Self::with_size(Self::DEFAULT_ROWS, Self::DEFAULT_COLS)
+    }
+
+    /// Create an empty board with the given dimensions, with the two outer columns
+    /// forbidden for the ball.
+    ///
+    /// Panics if the board is wider than `MAX_COLS`; see `try_with_size`.
+    #[must_use]
+    pub fn with_size(rows: usize, cols: usize) -> Self {
+        Self::try_with_size(rows, cols).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Like `with_size`, but a board wider than `MAX_COLS`, which the forbidden-column
+    /// mask cannot describe, is an error.
+    pub fn try_with_size(rows: usize, cols: usize) -> Result<Self, BoardError> {
+        if cols > Self::MAX_COLS {
+            return Err(BoardError::TooWide(cols));
+        }
+        Ok(Self {
             rows,
             cols,
             cells: vec![None; rows * cols],
+            ball: None,
             prev_tackle: None,
-        }
+            forbidden_cols: default_forbidden_mask(cols),
+            move_rules: MoveRules::default(),
+        })
+    }
+
+    /// Start a [`BoardBuilder`] for a board with default dimensions.
+    #[must_use]
+    pub fn builder() -> BoardBuilder {
+        BoardBuilder::new()
     }
+
     pub fn new_game() -> Self {
-        let rows = Self::DEFAULT_ROWS;
-        let cols = Self::DEFAULT_COLS;
-        let mut board = Self {
-            rows,
-            cols,
-            cells: vec![None; rows * cols],
-            prev_tackle: None,
-        };
+        Self::standard_start(&TeamSpec::STANDARD)
+    }
+
+    /// The start position for `team`: each side's defenders on its back row and its
+    /// attackers one row further in, spread evenly around the centre column, with the
+    /// ball on (2, 3). `TeamSpec::STANDARD` gives `new_game`.
+    ///
+    /// Panics if a row cannot hold the requested pieces.
+    ///
+    /// Example:
+    /// ```
+    /// use chessball::board::{ChessBallBoard, TeamSpec};
+    /// let team = TeamSpec { attackers: 3, defenders: 3 };
+    /// let board = ChessBallBoard::standard_start(&team);
+    /// assert!(board.validate().is_err());
+    /// assert_eq!(board.validate_with(&team), Ok(()));
+    /// ```
+    #[must_use]
+    pub fn standard_start(team: &TeamSpec) -> Self {
+        let mut board = Self::new();
         let (whiterow0, blackrow0) = (Self::DEFAULT_ROWS - 1, 0);
         let (whiterow1, blackrow1) = (whiterow0 - 1, 1);
-        for c in [1, 3, 5] {
-            board.place_piece(
-                Coord { r: blackrow0, c },
-                Piece {
-                    piece_type: PieceType::Defender,
-                    player: Player::Black,
-                },
-            );
-            board.place_piece(
-                Coord { r: whiterow0, c },
-                Piece {
-                    piece_type: PieceType::Defender,
-                    player: Player::White,
-                },
-            );
-        }
-        for c in [2, 4] {
-            board.place_piece(
-                Coord { r: blackrow1, c },
-                Piece {
-                    piece_type: PieceType::Attacker,
-                    player: Player::Black,
-                },
-            );
-            board.place_piece(
-                Coord { r: whiterow1, c },
-                Piece {
-                    piece_type: PieceType::Attacker,
-                    player: Player::White,
-                },
-            );
+        for (piece_type, count, rows) in [
+            (PieceType::Defender, team.defenders, (whiterow0, blackrow0)),
+            (PieceType::Attacker, team.attackers, (whiterow1, blackrow1)),
+        ] {
+            for c in spread_columns(count, board.cols) {
+                for (r, player) in [(rows.1, Player::Black), (rows.0, Player::White)] {
+                    board.place_piece(Coord { r, c }, Piece { piece_type, player });
+                }
+            }
         }
         board.place_ball(Coord { r: 2, c: 3 });
         board
     }
 
+    /// A random valid board of default size, drawn from `rng`: the ball on a column it
+    /// may be pushed into, and `n_white` White and `n_black` Black pieces, each an
+    /// attacker or a defender within `MAX_ATTACKERS` and `MAX_DEFENDERS`. Every piece
+    /// stands on its own square off both goal rows. The same `rng` state gives the same
+    /// board.
+    ///
+    /// Panics if a side asks for more than `MAX_ATTACKERS + MAX_DEFENDERS` pieces.
+    ///
+    /// Example:
+    /// ```
+    /// use chessball::board::ChessBallBoard;
+    /// use chessball::rng::XorShift64;
+    /// let board = ChessBallBoard::random(&mut XorShift64::new(1), 3, 2);
+    /// assert_eq!(board.validate(), Ok(()));
+    /// ```
+    #[must_use]
+    pub fn random(rng: &mut impl Rng, n_white: usize, n_black: usize) -> Self {
+        Self::random_with(rng, &TeamSpec::STANDARD, n_white, n_black)
+    }
+
+    /// `random` with the per-side limits of `team` instead of the standard ones; the
+    /// board passes `validate_with(team)`.
+    ///
+    /// Panics if a side asks for more than `team.attackers + team.defenders` pieces, or
+    /// for more pieces than fit off the goal rows.
+    #[must_use]
+    pub fn random_with(
+        rng: &mut impl Rng,
+        team: &TeamSpec,
+        n_white: usize,
+        n_black: usize,
+    ) -> Self {
+        let max = team.attackers + team.defenders;
+        assert!(
+            n_white <= max && n_black <= max,
+            "at most {max} pieces per side"
+        );
+        let mut board = Self::new();
+        let mut free: Vec<Coord> = board
+            .iter_coords()
+            .filter(|at| at.r != 0 && at.r != board.rows - 1)
+            .collect();
+        let ball_squares: Vec<usize> = (0..free.len())
+            .filter(|&i| !board.is_forbidden_col(free[i]))
+            .collect();
+        let ball = free.swap_remove(ball_squares[rng.choose_index(ball_squares.len())]);
+        board.place_ball(ball);
+
+        for (player, count) in [(Player::White, n_white), (Player::Black, n_black)] {
+            let (mut attackers, mut defenders) = (0, 0);
+            for _ in 0..count {
+                let attacker = if attackers == team.attackers {
+                    false
+                } else if defenders == team.defenders {
+                    true
+                } else {
+                    rng.choose_index(2) == 0
+                };
+                let piece_type = if attacker {
+                    attackers += 1;
+                    PieceType::Attacker
+                } else {
+                    defenders += 1;
+                    PieceType::Defender
+                };
+                let at = free.swap_remove(rng.choose_index(free.len()));
+                board.place_piece(at, Piece { piece_type, player });
+            }
+        }
+        board
+    }
+
     /// Convert (row, col) coordinates to vector index.
     ///
     /// Example:
@@ -266,7 +491,16 @@ impl ChessBallBoard {
             panic!("Invalid board coordinates.");
         }
         let i = self.idx(at);
+        let is_ball = piece.piece_type == PieceType::Ball;
         self.cells[i] = Some(piece);
+        if is_ball {
+            // Keep the first ball in scan order, as `scan_ball` would.
+            if self.ball.is_none_or(|ball| self.idx(ball) >= i) {
+                self.ball = Some(at);
+            }
+        } else if self.ball == Some(at) {
+            self.ball = self.scan_ball();
+        }
     }
 
     /// Place a piece at (r, c). Panics on out-of-bounds coordinates.
@@ -287,6 +521,9 @@ impl ChessBallBoard {
         }
         let i = self.idx(at);
         self.cells[i] = None;
+        if self.ball == Some(at) {
+            self.ball = self.scan_ball();
+        }
     }
 
     /// Get a reference to the piece at (r, c), or None. Panics on out-of-bounds.
@@ -305,6 +542,22 @@ impl ChessBallBoard {
     //     self.cells[self.idx(r, c)].as_mut()
     // }
 
+    /// The squares of row `r`, left to right. Panics if `r` is off the board.
+    #[must_use]
+    pub fn row(&self, r: usize) -> &[Option<Piece>] {
+        assert!(r < self.rows, "Invalid board coordinates.");
+        &self.cells[r * self.cols..(r + 1) * self.cols]
+    }
+
+    /// The squares of column `c`, from row 0 up. Panics if `c` is off the board.
+    #[must_use]
+    pub fn column(&self, c: usize) -> Vec<Option<&Piece>> {
+        assert!(c < self.cols, "Invalid board coordinates.");
+        (0..self.rows)
+            .map(|r| self.cells[r * self.cols + c].as_ref())
+            .collect()
+    }
+
     pub fn iter_coords(&self) -> impl std::iter::Iterator<Item = Coord> {
         (0..self.cols * self.rows).map(|i| Coord {
             r: (i / self.cols),
@@ -312,6 +565,68 @@ impl ChessBallBoard {
         })
     }
 
+    /// Iterate over the in-bounds neighbours of `at` (all 8 directions) with their contents.
+    ///
+    /// Example:
+    /// ```
+    /// use chessball::board::{ChessBallBoard, Coord};
+    /// let b = ChessBallBoard::new_game();
+    /// assert_eq!(b.neighbors(Coord { r: 0, c: 0 }).count(), 3);
+    /// ```
+    pub fn neighbors(&self, at: Coord) -> impl Iterator<Item = (Coord, Option<&Piece>)> {
+        self.directions().filter_map(move |delta| {
+            let coord = (at + delta)?;
+            Some((coord, self.get_piece(coord)))
+        })
+    }
+
+    /// The eight `DIRECTIONS`, bounded by this board's size rather than the default one.
+    pub fn directions(&self) -> impl Iterator<Item = CoordDelta> + use<> {
+        let (rows, cols) = (self.rows, self.cols);
+        DIRECTIONS.iter().map(move |&delta| CoordDelta {
+            rows,
+            cols,
+            ..delta
+        })
+    }
+
+    /// The `directions` a piece of type `piece_type` may move in under `move_rules`.
+    pub fn directions_for(
+        &self,
+        piece_type: PieceType,
+    ) -> impl Iterator<Item = CoordDelta> + use<> {
+        let (rows, cols) = (self.rows, self.cols);
+        self.move_rules.directions(piece_type).map(move |dir| {
+            let (r, c) = dir.delta();
+            CoordDelta { r, c, rows, cols }
+        })
+    }
+
+    /// List every square whose contents differ between `self` and `other`, as
+    /// `(coord, before, after)` in row-major order. Panics if the sizes differ.
+    ///
+    /// Example:
+    /// ```
+    /// use chessball::board::{ChessBallBoard, Coord};
+    /// let before = ChessBallBoard::new_game();
+    /// let mut after = before.clone();
+    /// after.remove_piece(Coord { r: 0, c: 1 });
+    /// assert_eq!(before.diff(&after).len(), 1);
+    /// ```
+    #[must_use]
+    pub fn diff(&self, other: &Self) -> Vec<(Coord, Option<Piece>, Option<Piece>)> {
+        assert!(
+            (self.rows, self.cols) == (other.rows, other.cols),
+            "Cannot diff boards of different sizes."
+        );
+        self.iter_coords()
+            .filter_map(|coord| {
+                let (before, after) = (self.get_piece(coord), other.get_piece(coord));
+                (before != after).then(|| (coord, before.cloned(), after.cloned()))
+            })
+            .collect()
+    }
+
     /// Find the ball on the board. Returns (row, col) or None if no ball.
     ///
     /// Example:
@@ -323,6 +638,12 @@ impl ChessBallBoard {
     /// ```
     #[must_use]
     pub fn find_ball(&self) -> Option<Coord> {
+        self.ball
+    }
+
+    /// Find the ball by scanning every square; `find_ball` returns the cached result.
+    #[must_use]
+    fn scan_ball(&self) -> Option<Coord> {
         for coord in self.iter_coords() {
             if let Some(p) = &self.cells[self.idx(coord)]
                 && p.piece_type == PieceType::Ball
@@ -333,32 +654,257 @@ impl ChessBallBoard {
         None
     }
 
-    /// Returns true if the column is forbidden for a ball destination (col 0 or last).
+    /// Number of balls on the board. `place_piece` does not stop a second ball from being
+    /// placed, so this is how callers check that a board still holds exactly one.
+    #[must_use]
+    pub fn ball_count(&self) -> usize {
+        self.cells
+            .iter()
+            .flatten()
+            .filter(|p| p.piece_type == PieceType::Ball)
+            .count()
+    }
+
+    /// Iterate over the occupied squares in row-major order, with their pieces.
+    pub fn pieces(&self) -> impl Iterator<Item = (Coord, &Piece)> {
+        self.iter_coords()
+            .zip(&self.cells)
+            .filter_map(|(at, cell)| cell.as_ref().map(|piece| (at, piece)))
+    }
+
+    /// Iterate over the `(row, col)` of every square holding no piece, in row-major order.
+    pub fn empty_squares(&self) -> impl Iterator<Item = (usize, usize)> {
+        let cols = self.cols;
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| cell.is_none())
+            .map(move |(i, _)| (i / cols, i % cols))
+    }
+
+    /// Number of squares holding no piece.
+    #[must_use]
+    pub fn empty_count(&self) -> usize {
+        self.cells.iter().filter(|cell| cell.is_none()).count()
+    }
+
+    /// Fraction of the squares holding a piece, the ball included, in `[0, 1]`.
+    #[must_use]
+    pub fn occupancy(&self) -> f64 {
+        let total = self.cells.len();
+        (total - self.empty_count()) as f64 / total as f64
+    }
+
+    /// Bit `r * cols + c` set for every occupied square, the ball included.
+    ///
+    /// Panics if the board has more than 64 squares.
+    #[must_use]
+    pub fn occupancy_mask(&self) -> u64 {
+        self.mask_where(|_| true)
+    }
+
+    /// Like `occupancy_mask`, restricted to the pieces of `player`; `Player::Neutral`
+    /// gives the ball's square.
+    #[must_use]
+    pub fn player_mask(&self, player: Player) -> u64 {
+        self.mask_where(|piece| piece.player == player)
+    }
+
+    fn mask_where(&self, keep: impl Fn(&Piece) -> bool) -> u64 {
+        assert!(self.cells.len() <= 64, "board too large for a u64 mask");
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| cell.as_ref().is_some_and(&keep))
+            .fold(0, |mask, (i, _)| mask | 1 << i)
+    }
+
+    /// The player whose goal row holds the ball, if any: Black wins on row 0 and White
+    /// on the last row.
+    #[must_use]
+    pub fn winner(&self) -> Option<Player> {
+        let ball = self.find_ball()?;
+        [Player::White, Player::Black]
+            .into_iter()
+            .find(|player| player.goal_row(self.rows) == Some(ball.r))
+    }
+
+    /// True when neither side can ever score, so the game can only be drawn.
+    ///
+    /// The rule is deliberately conservative: only the ball's own absence or a side with
+    /// no attacker or defender left rules out a goal (nothing else moves the ball), and
+    /// the position is only insufficient once that holds for both sides. A side with a
+    /// single piece may be hopeless in practice but still counts as able to score.
+    #[must_use]
+    pub fn is_insufficient_material(&self) -> bool {
+        if self.find_ball().is_none() {
+            return true;
+        }
+        let can_score = |player: Player| {
+            self.cells
+                .iter()
+                .flatten()
+                .any(|p| p.player == player && p.piece_type != PieceType::Ball)
+        };
+        !can_score(Player::White) && !can_score(Player::Black)
+    }
+
+    /// True when no push can ever bring the ball closer to a goal, however long the game
+    /// goes on: there is no ball, the ball's column and both neighbouring columns are
+    /// forbidden or off the board (every push lands in one of them, so the ball can never
+    /// move), or no piece on the board may move in a direction that changes rows (a push
+    /// moves the ball the way the pushing piece moves). A finished game is not dead.
+    ///
+    /// Sound but incomplete: a ball walled in by pieces is never reported, since the
+    /// pieces can step away.
+    #[must_use]
+    pub fn is_provably_dead(&self) -> bool {
+        let Some(ball) = self.find_ball() else {
+            return true;
+        };
+        if self.winner().is_some() {
+            return false;
+        }
+        let frozen = (ball.c.saturating_sub(1)..=ball.c + 1)
+            .all(|c| c >= self.cols || self.is_forbidden_col(Coord { r: ball.r, c }));
+        let lifter = self.pieces().any(|(_, piece)| {
+            piece.piece_type != PieceType::Ball
+                && self
+                    .move_rules
+                    .directions(piece.piece_type)
+                    .any(|dir| dir.delta().0 != 0)
+        });
+        frozen || !lifter
+    }
+
+    /// Returns true if the column is forbidden for a ball destination. By default these
+    /// are the outer columns, 0 and `cols - 1`; see `set_forbidden_cols`.
     #[must_use]
     pub fn is_forbidden_col(&self, coord: Coord) -> bool {
-        coord.c == 0 || coord.c == (self.cols - 1)
+        coord.c < 64 && self.forbidden_cols & (1 << coord.c) != 0
+    }
+
+    /// The columns forbidden for the ball, in increasing order.
+    pub fn forbidden_cols(&self) -> impl Iterator<Item = usize> + use<> {
+        let mask = self.forbidden_cols;
+        (0..self.cols.min(64)).filter(move |&c| mask & (1 << c) != 0)
+    }
+
+    /// Replace the columns the ball may not be pushed into, e.g. none for variants that
+    /// allow the whole width. Panics if a column is off the board.
+    pub fn set_forbidden_cols(&mut self, cols: impl IntoIterator<Item = usize>) {
+        self.forbidden_cols = 0;
+        for c in cols {
+            assert!(c < self.cols && c < 64, "Invalid board coordinates.");
+            self.forbidden_cols |= 1 << c;
+        }
+    }
+
+    #[must_use]
+    pub fn move_rules(&self) -> MoveRules {
+        self.move_rules
+    }
+
+    /// Replace the directions pieces may move in, e.g. `MoveRules::orthogonal_defenders()`.
+    pub fn set_move_rules(&mut self, rules: MoveRules) {
+        self.move_rules = rules;
+    }
+
+    /// Check that the board describes a playable position.
+    ///
+    /// A valid board holds exactly one ball, owned by `Player::Neutral`, every other
+    /// piece belongs to White or Black, and no side exceeds `MAX_ATTACKERS` attackers
+    /// or `MAX_DEFENDERS` defenders.
+    ///
+    /// Example:
+    /// ```
+    /// use chessball::board::{BoardError, ChessBallBoard};
+    /// assert!(ChessBallBoard::new_game().validate().is_ok());
+    /// assert_eq!(ChessBallBoard::new().validate(), Err(BoardError::BallCount(0)));
+    /// ```
+    pub fn validate(&self) -> Result<(), BoardError> {
+        self.validate_with(&TeamSpec::STANDARD)
+    }
+
+    /// `validate` with the per-side limits of `team` instead of the standard ones.
+    pub fn validate_with(&self, team: &TeamSpec) -> Result<(), BoardError> {
+        let mut balls = 0usize;
+        let mut counts = [[0usize; 2]; 2];
+        for coord in self.iter_coords() {
+            let Some(piece) = self.get_piece(coord) else {
+                continue;
+            };
+            match (piece.piece_type, piece.player) {
+                (PieceType::Ball, Player::Neutral) => balls += 1,
+                (PieceType::Ball, _) | (_, Player::Neutral) => {
+                    return Err(BoardError::InvalidOwner(coord));
+                }
+                (piece_type, player) => {
+                    let side = usize::from(player == Player::Black);
+                    let kind = usize::from(piece_type == PieceType::Defender);
+                    counts[side][kind] += 1;
+                }
+            }
+        }
+        if balls != 1 {
+            return Err(BoardError::BallCount(balls));
+        }
+        for (side, player) in [Player::White, Player::Black].into_iter().enumerate() {
+            for (kind, piece_type, max) in [
+                (0, PieceType::Attacker, team.attackers),
+                (1, PieceType::Defender, team.defenders),
+            ] {
+                let count = counts[side][kind];
+                if count > max {
+                    return Err(BoardError::TooManyPieces {
+                        player,
+                        piece_type,
+                        count,
+                        max,
+                    });
+                }
+            }
+        }
+        Ok(())
     }
 
     /// Parse the textual repr given by Display into a ChessBallBoard.
     ///
     /// The format uses ROWS lines, each with COLS tokens separated by spaces.
     /// '--' denotes empty, otherwise two chars: <PlayerInitial><PieceInitial>, e.g. 'WA', 'NB'.
+    /// An optional `forbidden:` line lists the columns forbidden for the ball, separated by
+    /// spaces, or `-` for none; without it the two outer columns are.
     ///
-    /// Returns Err if formatting is invalid.
+    /// Returns Err if formatting is invalid. Any number of pieces is accepted; see
+    /// `from_repr_with` to enforce a team size. A finished game, with the ball already
+    /// on a goal row, loads too; use `from_repr_strict` to reject it.
     pub fn from_repr(s: &str) -> Result<Self, String> {
-        let lines: Vec<&str> = s
+        let (forbidden, lines): (Vec<&str>, Vec<&str>) = s
             .lines()
             .map(|l| l.trim())
             .filter(|l| !l.is_empty())
-            .collect();
+            .partition(|l| l.starts_with(FORBIDDEN_PREFIX));
         let n_rows = lines.len();
-        let n_cols = (lines[0].len() + 1) / 3;
-        let mut board = ChessBallBoard {
-            rows: n_rows,
-            cols: n_cols,
-            cells: vec![None; n_rows * n_cols],
-            prev_tackle: None,
-        };
+        let n_cols = lines.first().map_or(0, |line| (line.len() + 1) / 3);
+        let mut board =
+            ChessBallBoard::try_with_size(n_rows, n_cols).map_err(|err| err.to_string())?;
+        match forbidden[..] {
+            [] => {}
+            [line] => {
+                let mut cols = Vec::new();
+                for token in line[FORBIDDEN_PREFIX.len()..].split_whitespace() {
+                    if token == "-" {
+                        continue;
+                    }
+                    match token.parse::<usize>() {
+                        Ok(c) if c < n_cols => cols.push(c),
+                        _ => return Err(format!("Invalid forbidden column '{}'", token)),
+                    }
+                }
+                board.set_forbidden_cols(cols);
+            }
+            _ => return Err("More than one forbidden: line".to_string()),
+        }
         if lines.len() != board.rows {
             return Err(format!("Expected {} rows, got {}", board.rows, lines.len()));
         }
@@ -397,39 +943,432 @@ impl ChessBallBoard {
         }
         Ok(board)
     }
-}
 
-/// Prints the board in a way consistent with from_repr
-impl fmt::Display for ChessBallBoard {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for coord in self.iter_coords() {
-            match &self.cells[self.idx(coord)] {
-                Some(piece) => write!(f, "{}", piece)?,
-                None => write!(f, "--")?,
-            }
-            if coord.c + 1 < self.cols {
-                write!(f, " ")?;
-            }
-            if coord.c == self.cols - 1 {
-                writeln!(f)?;
+    /// Like `from_repr`, but rejects a board whose game is already over: searching such a
+    /// position gives no move and a terminal score, which is rarely what the caller meant.
+    pub fn from_repr_strict(s: &str) -> Result<Self, String> {
+        let board = Self::from_repr(s)?;
+        if let Some(winner) = board.winner() {
+            return Err(format!(
+                "Ball already on {winner:?}'s goal row: the game is over"
+            ));
+        }
+        Ok(board)
+    }
+
+    /// Parse several `from_repr` boards from one text, separated by one or more blank
+    /// lines. An error names the 1-based position it occurred in.
+    pub fn from_repr_many(s: &str) -> Result<Vec<Self>, String> {
+        let mut blocks = vec![Vec::new()];
+        for line in s.lines() {
+            if line.trim().is_empty() {
+                if blocks.last().is_some_and(|block| !block.is_empty()) {
+                    blocks.push(Vec::new());
+                }
+            } else {
+                blocks.last_mut().unwrap().push(line);
             }
         }
-        Ok(())
+        blocks
+            .iter()
+            .filter(|block| !block.is_empty())
+            .enumerate()
+            .map(|(i, block)| {
+                Self::from_repr(&block.join("\n"))
+                    .map_err(|err| format!("position {}: {err}", i + 1))
+            })
+            .collect()
     }
-}
 
-/// 8 directions of adjacency: orthogonal + diagonal
-pub const DIRECTIONS: &[CoordDelta] = &[
-    CoordDelta {
-        r: -1,
-        c: 0,
-        rows: ChessBallBoard::DEFAULT_ROWS,
-        cols: ChessBallBoard::DEFAULT_COLS,
-    },
-    CoordDelta {
-        r: 1,
-        c: 0,
-        rows: ChessBallBoard::DEFAULT_ROWS,
+    /// Like `from_repr`, but the parsed board must also pass `validate_with(team)`.
+    pub fn from_repr_with(s: &str, team: &TeamSpec) -> Result<Self, String> {
+        let board = Self::from_repr(s)?;
+        board.validate_with(team).map_err(|err| err.to_string())?;
+        Ok(board)
+    }
+
+    /// The board reflected left-to-right: column `c` becomes column `cols - 1 - c`.
+    #[must_use]
+    pub fn mirror_horizontal(&self) -> Self {
+        let mirror = |at: Coord| Coord {
+            r: at.r,
+            c: self.cols - 1 - at.c,
+        };
+        self.transformed(mirror, |player| player, Direction::mirrored)
+    }
+
+    /// The lexicographically smaller of the board and its horizontal mirror, comparing
+    /// squares in row-major order, then the tackle memory, the forbidden columns and the
+    /// move rules. A board and its mirror share the same canonical form.
+    ///
+    /// A standalone helper for callers that want to treat mirror images as one
+    /// position; no search keys on it (the engine solver uses
+    /// `Position::canonical_horizontal`).
+    #[must_use]
+    pub fn canonical(&self) -> Self {
+        let mirrored = self.mirror_horizontal();
+        if (
+            &mirrored.cells,
+            &mirrored.prev_tackle,
+            mirrored.forbidden_cols,
+            mirrored.move_rules,
+        ) < (
+            &self.cells,
+            &self.prev_tackle,
+            self.forbidden_cols,
+            self.move_rules,
+        ) {
+            mirrored
+        } else {
+            self.clone()
+        }
+    }
+
+    /// The board seen from the other side: rows are reversed and White and Black swap
+    /// pieces, so evaluating it for Black matches evaluating `self` for White.
+    #[must_use]
+    pub fn flip_perspective(&self) -> Self {
+        let flip = |at: Coord| Coord {
+            r: self.rows - 1 - at.r,
+            c: at.c,
+        };
+        self.transformed(flip, Player::opponent, Direction::flipped)
+    }
+
+    /// The same board with White and Black pieces swapped. Unlike `flip_perspective`,
+    /// every piece, the ball included, stays on its square.
+    #[must_use]
+    pub fn swap_players(&self) -> Self {
+        self.transformed(|at| at, Player::opponent, |dir| dir)
+    }
+
+    /// The board after playing `mv`, a legal move on `self`; the method form of
+    /// `moves::apply_move`. Only the squares the move touches are written after the
+    /// single copy of `self`.
+    ///
+    /// Panics if `mv.from` is empty.
+    #[must_use]
+    pub fn with_move(&self, mv: &MoveInfo) -> Self {
+        apply_move(self, mv)
+    }
+
+    /// Copy of the board with every square moved by `map_coord`, every owner by
+    /// `map_player` and every allowed direction of the move rules by `map_dir`, which
+    /// must be the direction `map_coord` turns each step into.
+    fn transformed(
+        &self,
+        map_coord: impl Fn(Coord) -> Coord,
+        map_player: impl Fn(Player) -> Player,
+        map_dir: impl Fn(Direction) -> Direction,
+    ) -> Self {
+        let mut out = Self::with_size(self.rows, self.cols);
+        out.move_rules = self.move_rules.mapped(map_dir);
+        out.set_forbidden_cols(
+            self.forbidden_cols()
+                .map(|c| map_coord(Coord { r: 0, c }).c),
+        );
+        for coord in self.iter_coords() {
+            if let Some(piece) = self.get_piece(coord) {
+                out.place_piece(
+                    map_coord(coord),
+                    Piece {
+                        piece_type: piece.piece_type,
+                        player: map_player(piece.player),
+                    },
+                );
+            }
+        }
+        out.prev_tackle = self.prev_tackle.as_ref().map(|tackle| DefenderTackle {
+            pushed_piece_from: map_coord(tackle.pushed_piece_from),
+            pushed_piece_to: map_coord(tackle.pushed_piece_to),
+            chain: tackle.chain.iter().map(|&at| map_coord(at)).collect(),
+        });
+        out
+    }
+
+    /// Render the board like `Display`, with White pieces, Black pieces and the ball
+    /// colored using ANSI escape codes.
+    pub fn render_colored(&self) -> String {
+        self.render(true)
+    }
+
+    /// Render the board, colored if `color` is set (see `render_colored`) and
+    /// identical to the `Display` output otherwise, e.g. when not writing to a terminal.
+    pub fn render(&self, color: bool) -> String {
+        if !color {
+            return self.to_string();
+        }
+        let mut out = String::new();
+        for r in 0..self.rows {
+            let cells = (0..self.cols)
+                .map(|c| match self.get_piece(Coord { r, c }) {
+                    Some(piece) => {
+                        let code = match piece.player {
+                            Player::White => ANSI_WHITE,
+                            Player::Black => ANSI_BLACK,
+                            Player::Neutral => ANSI_BALL,
+                        };
+                        format!("{}{}{}", code, piece, ANSI_RESET)
+                    }
+                    None => format!("{}--{}", ANSI_EMPTY, ANSI_RESET),
+                })
+                .collect::<Vec<_>>();
+            out.push_str(&cells.join(" "));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+const ANSI_WHITE: &str = "\x1b[1;97m";
+const ANSI_BLACK: &str = "\x1b[1;34m";
+const ANSI_BALL: &str = "\x1b[1;33m";
+const ANSI_EMPTY: &str = "\x1b[2m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// Prints the board in a way consistent with from_repr. Forbidden columns other than the
+/// two outer ones follow the grid on a `forbidden:` line.
+impl fmt::Display for ChessBallBoard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for coord in self.iter_coords() {
+            match &self.cells[self.idx(coord)] {
+                Some(piece) => write!(f, "{}", piece)?,
+                None => write!(f, "--")?,
+            }
+            if coord.c + 1 < self.cols {
+                write!(f, " ")?;
+            }
+            if coord.c == self.cols - 1 {
+                writeln!(f)?;
+            }
+        }
+        if self.forbidden_cols != default_forbidden_mask(self.cols) {
+            let cols: Vec<String> = self.forbidden_cols().map(|c| c.to_string()).collect();
+            if cols.is_empty() {
+                writeln!(f, "{FORBIDDEN_PREFIX} -")?;
+            } else {
+                writeln!(f, "{FORBIDDEN_PREFIX} {}", cols.join(" "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Start of the line listing non-default forbidden columns in the text format.
+const FORBIDDEN_PREFIX: &str = "forbidden:";
+
+/// Errors raised when a board fails validation, or a player or piece type fails to parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoardError {
+    /// A coordinate lies outside the board.
+    OutOfBounds(Coord),
+    /// Two pieces were placed on the same square.
+    Occupied(Coord),
+    /// The board does not hold exactly one ball.
+    BallCount(usize),
+    /// A ball owned by a player, or a non-ball piece owned by `Player::Neutral`.
+    InvalidOwner(Coord),
+    /// A side has more pieces of one type than the rules allow.
+    TooManyPieces {
+        player: Player,
+        piece_type: PieceType,
+        count: usize,
+        max: usize,
+    },
+    /// A string that names no player.
+    UnknownPlayer(String),
+    /// A string that names no piece type.
+    UnknownPieceType(String),
+    /// A board with more columns than `ChessBallBoard::MAX_COLS`.
+    TooWide(usize),
+}
+
+impl fmt::Display for BoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoardError::OutOfBounds(at) => {
+                write!(f, "coordinate {},{} is off the board", at.r, at.c)
+            }
+            BoardError::Occupied(at) => write!(f, "square {},{} is already occupied", at.r, at.c),
+            BoardError::BallCount(n) => write!(f, "expected exactly one ball, found {}", n),
+            BoardError::InvalidOwner(at) => {
+                write!(f, "piece at {},{} has an invalid owner", at.r, at.c)
+            }
+            BoardError::TooManyPieces {
+                player,
+                piece_type,
+                count,
+                max,
+            } => write!(
+                f,
+                "{:?} has {} pieces of type {:?}, at most {} allowed",
+                player, count, piece_type, max
+            ),
+            BoardError::UnknownPlayer(token) => write!(f, "unknown player '{}'", token),
+            BoardError::UnknownPieceType(token) => write!(f, "unknown piece type '{}'", token),
+            BoardError::TooWide(cols) => write!(
+                f,
+                "a board {} columns wide exceeds the maximum of {}",
+                cols,
+                ChessBallBoard::MAX_COLS
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BoardError {}
+
+/// Chained construction of boards, validated once at the end.
+///
+/// Example:
+/// ```
+/// use chessball::board::{BoardBuilder, Player};
+/// let board = BoardBuilder::new()
+///     .defender(Player::White, 3, 3)
+///     .ball(2, 3)
+///     .build()
+///     .unwrap();
+/// assert_eq!(board.find_ball().map(|at| (at.r, at.c)), Some((2, 3)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BoardBuilder {
+    rows: usize,
+    cols: usize,
+    placements: Vec<(Coord, Piece)>,
+    forbidden_cols: Option<Vec<usize>>,
+    move_rules: MoveRules,
+    team: TeamSpec,
+}
+
+impl Default for BoardBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BoardBuilder {
+    /// Start an empty builder with the default board dimensions.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            rows: ChessBallBoard::DEFAULT_ROWS,
+            cols: ChessBallBoard::DEFAULT_COLS,
+            placements: Vec::new(),
+            forbidden_cols: None,
+            move_rules: MoveRules::default(),
+            team: TeamSpec::STANDARD,
+        }
+    }
+
+    /// Override the board dimensions.
+    #[must_use]
+    pub fn size(mut self, rows: usize, cols: usize) -> Self {
+        self.rows = rows;
+        self.cols = cols;
+        self
+    }
+
+    /// Place an attacker owned by `player` at (r, c).
+    #[must_use]
+    pub fn attacker(self, player: Player, r: usize, c: usize) -> Self {
+        self.piece(
+            Coord { r, c },
+            Piece {
+                piece_type: PieceType::Attacker,
+                player,
+            },
+        )
+    }
+
+    /// Place a defender owned by `player` at (r, c).
+    #[must_use]
+    pub fn defender(self, player: Player, r: usize, c: usize) -> Self {
+        self.piece(
+            Coord { r, c },
+            Piece {
+                piece_type: PieceType::Defender,
+                player,
+            },
+        )
+    }
+
+    /// Place the ball at (r, c).
+    #[must_use]
+    pub fn ball(self, r: usize, c: usize) -> Self {
+        self.piece(
+            Coord { r, c },
+            Piece {
+                piece_type: PieceType::Ball,
+                player: Player::Neutral,
+            },
+        )
+    }
+
+    /// Place an arbitrary piece.
+    #[must_use]
+    pub fn piece(mut self, at: Coord, piece: Piece) -> Self {
+        self.placements.push((at, piece));
+        self
+    }
+
+    /// Forbid the ball from exactly these columns instead of the two outer ones.
+    #[must_use]
+    pub fn forbidden_cols(mut self, cols: &[usize]) -> Self {
+        self.forbidden_cols = Some(cols.to_vec());
+        self
+    }
+
+    /// Restrict the directions pieces may move in.
+    #[must_use]
+    pub fn move_rules(mut self, rules: MoveRules) -> Self {
+        self.move_rules = rules;
+        self
+    }
+
+    /// Validate against the limits of `team` instead of the standard ones.
+    #[must_use]
+    pub fn team(mut self, team: TeamSpec) -> Self {
+        self.team = team;
+        self
+    }
+
+    /// Build the board, rejecting off-board or overlapping placements and boards
+    /// that fail [`ChessBallBoard::validate_with`] for the builder's team.
+    pub fn build(self) -> Result<ChessBallBoard, BoardError> {
+        let mut board = ChessBallBoard::try_with_size(self.rows, self.cols)?;
+        board.set_move_rules(self.move_rules);
+        if let Some(cols) = self.forbidden_cols {
+            if let Some(&c) = cols.iter().find(|&&c| c >= self.cols) {
+                return Err(BoardError::OutOfBounds(Coord { r: 0, c }));
+            }
+            board.set_forbidden_cols(cols);
+        }
+        for (at, piece) in self.placements {
+            if !board.is_on_board(at) {
+                return Err(BoardError::OutOfBounds(at));
+            }
+            if board.get_piece(at).is_some() {
+                return Err(BoardError::Occupied(at));
+            }
+            board.place_piece(at, piece);
+        }
+        board.validate_with(&self.team)?;
+        Ok(board)
+    }
+}
+
+/// 8 directions of adjacency: orthogonal + diagonal
+pub const DIRECTIONS: &[CoordDelta] = &[
+    CoordDelta {
+        r: -1,
+        c: 0,
+        rows: ChessBallBoard::DEFAULT_ROWS,
+        cols: ChessBallBoard::DEFAULT_COLS,
+    },
+    CoordDelta {
+        r: 1,
+        c: 0,
+        rows: ChessBallBoard::DEFAULT_ROWS,
         cols: ChessBallBoard::DEFAULT_COLS,
     },
     CoordDelta {
@@ -470,9 +1409,249 @@ pub const DIRECTIONS: &[CoordDelta] = &[
     },
 ];
 
+/// One of the eight `DIRECTIONS` by name. North is towards row 0, Black's goal;
+/// East is towards the last column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    South,
+    West,
+    East,
+    NorthWest,
+    NorthEast,
+    SouthWest,
+    SouthEast,
+}
+
+impl Direction {
+    const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::South,
+        Direction::West,
+        Direction::East,
+        Direction::NorthWest,
+        Direction::NorthEast,
+        Direction::SouthWest,
+        Direction::SouthEast,
+    ];
+
+    /// Every direction, in the same order as `DIRECTIONS`.
+    pub fn all() -> impl Iterator<Item = Direction> {
+        Self::ALL.into_iter()
+    }
+
+    /// North, South, West and East.
+    pub fn orthogonal() -> impl Iterator<Item = Direction> {
+        Self::all().filter(|d| !d.is_diagonal())
+    }
+
+    /// The four diagonal directions.
+    pub fn diagonal() -> impl Iterator<Item = Direction> {
+        Self::all().filter(|d| d.is_diagonal())
+    }
+
+    #[must_use]
+    pub fn is_diagonal(self) -> bool {
+        let (dr, dc) = self.delta();
+        dr != 0 && dc != 0
+    }
+
+    /// The direction reflected left-to-right, as `mirror_horizontal` moves a step: East
+    /// and West swap.
+    #[must_use]
+    pub fn mirrored(self) -> Self {
+        let (dr, dc) = self.delta();
+        Self::from_delta(dr, -dc)
+    }
+
+    /// The direction reflected top-to-bottom, as `flip_perspective` moves a step: North
+    /// and South swap.
+    #[must_use]
+    pub fn flipped(self) -> Self {
+        let (dr, dc) = self.delta();
+        Self::from_delta(-dr, dc)
+    }
+
+    fn from_delta(dr: isize, dc: isize) -> Self {
+        *Self::ALL
+            .iter()
+            .find(|dir| dir.delta() == (dr, dc))
+            .expect("not a unit step")
+    }
+
+    /// The `(dr, dc)` offset of one step in this direction.
+    #[must_use]
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::North => (-1, 0),
+            Direction::South => (1, 0),
+            Direction::West => (0, -1),
+            Direction::East => (0, 1),
+            Direction::NorthWest => (-1, -1),
+            Direction::NorthEast => (-1, 1),
+            Direction::SouthWest => (1, -1),
+            Direction::SouthEast => (1, 1),
+        }
+    }
+}
+
+/// The directions attackers and defenders may move, jump, push and tackle in, and
+/// whether tackles shove whole lines. The default allows all eight directions for both
+/// and single-piece tackles; the ball only moves when pushed, so it follows its pusher.
+///
+/// Example:
+/// ```
+/// use chessball::board::{Direction, MoveRules, PieceType};
+/// let rules = MoveRules::orthogonal_defenders();
+/// assert!(!rules.allows(PieceType::Defender, Direction::NorthEast));
+/// assert!(rules.allows(PieceType::Attacker, Direction::NorthEast));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MoveRules {
+    /// One bit per `Direction`, in `Direction::all` order.
+    attacker: u8,
+    defender: u8,
+    chain_tackle: bool,
+}
+
+impl Default for MoveRules {
+    fn default() -> Self {
+        Self {
+            attacker: u8::MAX,
+            defender: u8::MAX,
+            chain_tackle: false,
+        }
+    }
+}
+
+impl MoveRules {
+    /// The variant where defenders move only orthogonally and attackers keep diagonals.
+    #[must_use]
+    pub fn orthogonal_defenders() -> Self {
+        Self::default().with_directions(PieceType::Defender, Direction::orthogonal())
+    }
+
+    /// Allow pieces of `piece_type` exactly the given directions. The ball's
+    /// directions cannot be restricted, so for it this is a no-op.
+    #[must_use]
+    pub fn with_directions(
+        mut self,
+        piece_type: PieceType,
+        directions: impl IntoIterator<Item = Direction>,
+    ) -> Self {
+        let mask = directions
+            .into_iter()
+            .fold(0, |mask, dir| mask | Self::bit(dir));
+        match piece_type {
+            PieceType::Attacker => self.attacker = mask,
+            PieceType::Defender => self.defender = mask,
+            PieceType::Ball => {}
+        }
+        self
+    }
+
+    /// Let a tackle shove a contiguous line of opponent pieces one square on, provided
+    /// the square beyond the line is empty and on the board.
+    #[must_use]
+    pub fn with_chain_tackle(mut self, chain_tackle: bool) -> Self {
+        self.chain_tackle = chain_tackle;
+        self
+    }
+
+    #[must_use]
+    pub fn chain_tackle(&self) -> bool {
+        self.chain_tackle
+    }
+
+    #[must_use]
+    pub fn allows(&self, piece_type: PieceType, dir: Direction) -> bool {
+        let mask = match piece_type {
+            PieceType::Attacker => self.attacker,
+            PieceType::Defender => self.defender,
+            PieceType::Ball => u8::MAX,
+        };
+        mask & Self::bit(dir) != 0
+    }
+
+    /// The directions allowed to `piece_type`, in `Direction::all` order.
+    pub fn directions(&self, piece_type: PieceType) -> impl Iterator<Item = Direction> + use<> {
+        let rules = *self;
+        Direction::all().filter(move |&dir| rules.allows(piece_type, dir))
+    }
+
+    /// The same rules with every allowed direction replaced by `map_dir` of it.
+    fn mapped(self, map_dir: impl Fn(Direction) -> Direction) -> Self {
+        let map_mask = |mask: u8| {
+            Direction::all()
+                .filter(|&dir| mask & Self::bit(dir) != 0)
+                .fold(0, |out, dir| out | Self::bit(map_dir(dir)))
+        };
+        Self {
+            attacker: map_mask(self.attacker),
+            defender: map_mask(self.defender),
+            ..self
+        }
+    }
+
+    fn bit(dir: Direction) -> u8 {
+        1 << dir as u8
+    }
+}
+
+/// How many attackers and defenders each side fields: the limits `validate_with`
+/// enforces and the pieces `standard_start` places.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TeamSpec {
+    pub attackers: usize,
+    pub defenders: usize,
+}
+
+impl TeamSpec {
+    /// Two attackers and three defenders per side, as in `new_game`.
+    pub const STANDARD: Self = Self {
+        attackers: 2,
+        defenders: 3,
+    };
+}
+
+impl Default for TeamSpec {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+/// The forbidden-column mask of a fresh board `cols` wide: its outer columns, if any.
+fn default_forbidden_mask(cols: usize) -> u64 {
+    match cols {
+        0 => 0,
+        _ => 1 | 1 << (cols - 1),
+    }
+}
+
+/// Columns for `count` pieces in a row of `cols`, centred and two apart when they fit,
+/// otherwise side by side.
+fn spread_columns(count: usize, cols: usize) -> Vec<usize> {
+    assert!(count <= cols, "{count} pieces do not fit in {cols} columns");
+    let step = if count == 0 || 2 * count - 1 <= cols {
+        2
+    } else {
+        1
+    };
+    let width = (count * step).saturating_sub(step - 1);
+    let start = (cols - width) / 2;
+    (0..count).map(|i| start + i * step).collect()
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::board::{ChessBallBoard, Coord, Piece, PieceType, Player};
+    use std::collections::HashSet;
+
+    use crate::board::{
+        BoardBuilder, BoardError, ChessBallBoard, Coord, DIRECTIONS, Direction, MoveRules, Piece,
+        PieceType, Player, TeamSpec,
+    };
+    use crate::moves::possible_moves;
+    use crate::rng::XorShift64;
 
     #[test]
     fn test_board_from_repr_and_display_roundtrip() {
@@ -630,4 +1809,680 @@ mod tests {
         let out = format!("{}", b);
         assert_eq!(out, s);
     }
+
+    #[test]
+    fn test_builder_start_position() {
+        let built = BoardBuilder::new()
+            .defender(Player::Black, 0, 1)
+            .defender(Player::Black, 0, 3)
+            .defender(Player::Black, 0, 5)
+            .attacker(Player::Black, 1, 2)
+            .attacker(Player::Black, 1, 4)
+            .ball(2, 3)
+            .attacker(Player::White, 4, 2)
+            .attacker(Player::White, 4, 4)
+            .defender(Player::White, 5, 1)
+            .defender(Player::White, 5, 3)
+            .defender(Player::White, 5, 5)
+            .build()
+            .unwrap();
+        assert_eq!(built, ChessBallBoard::new_game());
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_boards() {
+        assert_eq!(
+            BoardBuilder::new().defender(Player::White, 0, 0).build(),
+            Err(BoardError::BallCount(0))
+        );
+        assert_eq!(
+            BoardBuilder::new()
+                .ball(2, 3)
+                .attacker(Player::White, 2, 3)
+                .build(),
+            Err(BoardError::Occupied(Coord { r: 2, c: 3 }))
+        );
+        assert_eq!(
+            BoardBuilder::new().size(3, 3).ball(3, 0).build(),
+            Err(BoardError::OutOfBounds(Coord { r: 3, c: 0 }))
+        );
+        assert_eq!(
+            BoardBuilder::new()
+                .ball(2, 3)
+                .attacker(Player::White, 4, 1)
+                .attacker(Player::White, 4, 2)
+                .attacker(Player::White, 4, 3)
+                .build(),
+            Err(BoardError::TooManyPieces {
+                player: Player::White,
+                piece_type: PieceType::Attacker,
+                count: 3,
+                max: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn test_render_colored() {
+        let board = ChessBallBoard::new_game();
+        let colored = board.render_colored();
+        assert!(colored.contains("\x1b[1;97mWA\x1b[0m"));
+        assert!(colored.contains("\x1b[1;34mBD\x1b[0m"));
+        assert!(colored.contains("\x1b[1;33mNB\x1b[0m"));
+        assert_eq!(colored.lines().count(), board.rows);
+
+        let plain = board.render(false);
+        assert!(!plain.contains('\x1b'));
+        assert_eq!(plain, board.to_string());
+        assert!(plain.contains("WA") && plain.contains("BD") && plain.contains("NB"));
+    }
+
+    #[test]
+    fn test_cached_ball_matches_scan() {
+        let mut board = ChessBallBoard::new();
+        assert_eq!(board.find_ball(), board.scan_ball());
+        board.place_ball(Coord { r: 3, c: 3 });
+        board.place_ball(Coord { r: 1, c: 2 });
+        assert_eq!(board.find_ball(), Some(Coord { r: 1, c: 2 }));
+        board.remove_piece(Coord { r: 1, c: 2 });
+        assert_eq!(board.find_ball(), Some(Coord { r: 3, c: 3 }));
+        board.place_piece(
+            Coord { r: 3, c: 3 },
+            Piece {
+                piece_type: PieceType::Defender,
+                player: Player::White,
+            },
+        );
+        assert_eq!(board.find_ball(), None);
+        assert_eq!(board.find_ball(), board.scan_ball());
+
+        let board = BoardBuilder::new()
+            .defender(Player::White, 2, 3)
+            .ball(2, 4)
+            .build()
+            .unwrap();
+        let (_, pushed) = crate::moves::possible_moves(&board, Player::White)
+            .into_iter()
+            .find(|(mv, _)| mv.to == Coord { r: 2, c: 4 })
+            .unwrap();
+        assert_eq!(pushed.find_ball(), Some(Coord { r: 2, c: 5 }));
+        assert_eq!(pushed.find_ball(), pushed.scan_ball());
+    }
+
+    #[test]
+    fn test_mirror_and_flip() {
+        let board = BoardBuilder::new()
+            .ball(2, 3)
+            .attacker(Player::White, 4, 1)
+            .defender(Player::Black, 0, 5)
+            .build()
+            .unwrap();
+        let mirrored = board.mirror_horizontal();
+        assert_eq!(mirrored.find_ball(), Some(Coord { r: 2, c: 3 }));
+        assert_eq!(
+            mirrored.get_piece(Coord { r: 4, c: 5 }).map(|p| p.player),
+            Some(Player::White)
+        );
+        let flipped = board.flip_perspective();
+        assert_eq!(flipped.find_ball(), Some(Coord { r: 3, c: 3 }));
+        assert_eq!(
+            flipped.get_piece(Coord { r: 1, c: 1 }).map(|p| p.player),
+            Some(Player::Black)
+        );
+        assert_eq!(
+            flipped.get_piece(Coord { r: 5, c: 5 }).map(|p| p.player),
+            Some(Player::White)
+        );
+        assert_eq!(mirrored.mirror_horizontal(), board);
+        assert_eq!(flipped.flip_perspective(), board);
+    }
+
+    #[test]
+    fn test_empty_count_and_occupancy() {
+        let board = ChessBallBoard::new_game();
+        let pieces = board
+            .iter_coords()
+            .filter(|&at| board.get_piece(at).is_some())
+            .count();
+        assert_eq!(pieces, 11);
+        assert_eq!(board.empty_count(), board.rows * board.cols - pieces);
+        assert!((board.occupancy() - 11.0 / 42.0).abs() < 1e-12);
+        assert_eq!(ChessBallBoard::new().occupancy(), 0.0);
+    }
+
+    #[test]
+    fn test_pieces_and_empty_squares_cover_the_board() {
+        let board = ChessBallBoard::new_game();
+        assert_eq!(
+            board.pieces().count() + board.empty_squares().count(),
+            board.rows * board.cols
+        );
+        assert_eq!(board.empty_squares().count(), board.empty_count());
+        assert!(
+            board
+                .empty_squares()
+                .all(|(r, c)| board.get_piece(Coord { r, c }).is_none())
+        );
+        assert_eq!(
+            board
+                .pieces()
+                .find(|(_, p)| p.piece_type == PieceType::Ball)
+                .map(|(at, _)| at),
+            board.find_ball()
+        );
+    }
+
+    #[test]
+    fn test_occupancy_masks_match_the_pieces() {
+        let board = ChessBallBoard::new_game();
+        let mask = board.occupancy_mask();
+        assert_eq!(mask.count_ones() as usize, board.pieces().count());
+        for (at, _) in board.pieces() {
+            assert_ne!(mask & 1 << (at.r * board.cols + at.c), 0);
+        }
+        let white = board.player_mask(Player::White);
+        let black = board.player_mask(Player::Black);
+        let ball = board.player_mask(Player::Neutral);
+        assert_eq!(white | black | ball, mask);
+        assert_eq!(white & black, 0);
+        assert_eq!(ball.count_ones(), 1);
+        assert_eq!(
+            white.count_ones() as usize,
+            board
+                .pieces()
+                .filter(|(_, p)| p.player == Player::White)
+                .count()
+        );
+    }
+
+    #[test]
+    fn test_swap_players() {
+        let board = ChessBallBoard::new_game();
+        let swapped = board.swap_players();
+        assert_eq!(swapped.find_ball(), board.find_ball());
+        assert_eq!(
+            swapped.get_piece(Coord { r: 0, c: 1 }).map(|p| p.player),
+            Some(Player::White)
+        );
+        assert_eq!(swapped.swap_players(), board);
+        assert_eq!(
+            possible_moves(&board, Player::White).len(),
+            possible_moves(&swapped, Player::Black).len()
+        );
+    }
+
+    #[test]
+    fn test_canonical_identifies_mirrors() {
+        let board = BoardBuilder::new()
+            .ball(2, 3)
+            .attacker(Player::White, 4, 1)
+            .defender(Player::Black, 0, 5)
+            .build()
+            .unwrap();
+        let mirrored = board.mirror_horizontal();
+        assert_ne!(board, mirrored);
+        assert_eq!(board.canonical(), mirrored.canonical());
+        assert!(board.canonical() == board || board.canonical() == mirrored);
+        assert_eq!(board.canonical().canonical(), board.canonical());
+
+        let start = ChessBallBoard::new_game();
+        assert_eq!(start.canonical(), start);
+
+        // Symmetric squares, but attackers may only move east: the mirror differs in its
+        // rules alone and must still share the canonical form.
+        let east_only =
+            MoveRules::default().with_directions(PieceType::Attacker, [Direction::East]);
+        let board = BoardBuilder::new()
+            .move_rules(east_only)
+            .ball(2, 3)
+            .attacker(Player::White, 4, 1)
+            .attacker(Player::White, 4, 5)
+            .build()
+            .unwrap();
+        let mirrored = board.mirror_horizontal();
+        assert_ne!(board, mirrored);
+        assert_eq!(board.canonical(), mirrored.canonical());
+    }
+
+    #[test]
+    fn test_forbidden_cols() {
+        let board = ChessBallBoard::new();
+        assert_eq!(board.forbidden_cols().collect::<Vec<_>>(), vec![0, 6]);
+        let parsed = ChessBallBoard::from_repr(&ChessBallBoard::new_game().to_string()).unwrap();
+        assert_eq!(parsed.forbidden_cols().collect::<Vec<_>>(), vec![0, 6]);
+
+        let mut variant = ChessBallBoard::new();
+        variant.set_forbidden_cols([1]);
+        assert!(variant.is_forbidden_col(Coord { r: 3, c: 1 }));
+        assert!(!variant.is_forbidden_col(Coord { r: 3, c: 0 }));
+        assert_eq!(
+            variant
+                .mirror_horizontal()
+                .forbidden_cols()
+                .collect::<Vec<_>>(),
+            vec![5]
+        );
+        assert_eq!(
+            BoardBuilder::new().ball(2, 3).forbidden_cols(&[7]).build(),
+            Err(BoardError::OutOfBounds(Coord { r: 0, c: 7 }))
+        );
+    }
+
+    #[test]
+    fn test_row_and_column() {
+        let board = ChessBallBoard::new_game();
+        let white_defender = Piece {
+            piece_type: PieceType::Defender,
+            player: Player::White,
+        };
+        let back_row: Vec<Option<Piece>> = (0..7)
+            .map(|c| (c % 2 == 1).then(|| white_defender.clone()))
+            .collect();
+        assert_eq!(board.row(5), back_row.as_slice());
+        assert!(board.row(3).iter().all(Option::is_none));
+
+        let ball = Piece {
+            piece_type: PieceType::Ball,
+            player: Player::Neutral,
+        };
+        let centre: Vec<Option<&Piece>> =
+            (0..6).map(|r| board.get_piece(Coord { r, c: 3 })).collect();
+        assert_eq!(board.column(3), centre);
+        assert_eq!(board.column(3)[2], Some(&ball));
+        assert!(board.column(0).iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_parse_player_and_piece_type() {
+        for (token, player) in [
+            ("White", Player::White),
+            ("B", Player::Black),
+            ("neutral", Player::Neutral),
+            ("w", Player::White),
+        ] {
+            assert_eq!(token.parse::<Player>(), Ok(player));
+        }
+        for (token, piece_type) in [
+            ("Attacker", PieceType::Attacker),
+            ("D", PieceType::Defender),
+            ("ball", PieceType::Ball),
+        ] {
+            assert_eq!(token.parse::<PieceType>(), Ok(piece_type));
+        }
+
+        for token in ["", "Whit", "X", "White "] {
+            assert_eq!(
+                token.parse::<Player>(),
+                Err(BoardError::UnknownPlayer(token.to_string()))
+            );
+        }
+        for token in ["", "Pawn", "W"] {
+            assert_eq!(
+                token.parse::<PieceType>(),
+                Err(BoardError::UnknownPieceType(token.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_insufficient_material() {
+        assert!(!ChessBallBoard::new_game().is_insufficient_material());
+
+        // Black has nothing left, but White can still push the ball home.
+        let white_only = BoardBuilder::new()
+            .ball(2, 3)
+            .attacker(Player::White, 1, 3)
+            .build()
+            .unwrap();
+        assert!(!white_only.is_insufficient_material());
+
+        let ball_only = BoardBuilder::new().ball(2, 3).build().unwrap();
+        assert!(ball_only.is_insufficient_material());
+        assert!(ChessBallBoard::new().is_insufficient_material());
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let board = ChessBallBoard::new_game();
+        assert_eq!(board.neighbors(Coord { r: 2, c: 3 }).count(), 8);
+        assert_eq!(board.neighbors(Coord { r: 0, c: 0 }).count(), 3);
+        assert_eq!(board.neighbors(Coord { r: 5, c: 6 }).count(), 3);
+        assert_eq!(board.neighbors(Coord { r: 0, c: 3 }).count(), 5);
+        let occupied: Vec<Coord> = board
+            .neighbors(Coord { r: 1, c: 3 })
+            .filter_map(|(coord, piece)| piece.map(|_| coord))
+            .collect();
+        assert_eq!(
+            occupied,
+            vec![
+                Coord { r: 0, c: 3 },
+                Coord { r: 2, c: 3 },
+                Coord { r: 1, c: 2 },
+                Coord { r: 1, c: 4 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_simple_move_and_push() {
+        let board = BoardBuilder::new()
+            .defender(Player::White, 2, 3)
+            .ball(2, 4)
+            .build()
+            .unwrap();
+        let moves = crate::moves::possible_moves(&board, Player::White);
+        let defender = Some(Piece {
+            piece_type: PieceType::Defender,
+            player: Player::White,
+        });
+
+        let (_, stepped) = moves
+            .iter()
+            .find(|(mv, _)| mv.to == Coord { r: 1, c: 3 })
+            .unwrap();
+        assert_eq!(
+            board.diff(stepped),
+            vec![
+                (Coord { r: 1, c: 3 }, None, defender.clone()),
+                (Coord { r: 2, c: 3 }, defender.clone(), None),
+            ]
+        );
+
+        let (_, pushed) = moves
+            .iter()
+            .find(|(mv, _)| mv.to == Coord { r: 2, c: 4 })
+            .unwrap();
+        let diff = board.diff(pushed);
+        assert_eq!(diff.len(), 3);
+        assert_eq!(diff[0], (Coord { r: 2, c: 3 }, defender.clone(), None));
+        assert_eq!(diff[1].2, defender);
+        assert_eq!(diff[2].0, Coord { r: 2, c: 5 });
+        assert!(board.diff(&board).is_empty());
+    }
+
+    #[test]
+    fn test_direction_matches_directions() {
+        let named: Vec<(isize, isize)> = Direction::all().map(Direction::delta).collect();
+        let anonymous: Vec<(isize, isize)> = DIRECTIONS.iter().map(|d| (d.r, d.c)).collect();
+        assert_eq!(named, anonymous);
+        assert_eq!(Direction::orthogonal().count(), 4);
+        assert!(Direction::diagonal().all(Direction::is_diagonal));
+        assert!(!Direction::orthogonal().any(Direction::is_diagonal));
+    }
+
+    #[test]
+    fn test_with_move_matches_possible_moves() {
+        let board = ChessBallBoard::new_game();
+        for player in [Player::White, Player::Black] {
+            for (mv, after) in possible_moves(&board, player) {
+                assert_eq!(board.with_move(&mv), after);
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_is_reproducible_and_valid() {
+        let a = ChessBallBoard::random(&mut XorShift64::new(42), 5, 4);
+        let b = ChessBallBoard::random(&mut XorShift64::new(42), 5, 4);
+        assert_eq!(a, b);
+        assert_eq!(a.validate(), Ok(()));
+        assert_eq!(a.pieces().count(), 10);
+        assert!(a.pieces().all(|(at, _)| at.r != 0 && at.r != a.rows - 1));
+
+        let mut rng = XorShift64::new(7);
+        for _ in 0..50 {
+            let board = ChessBallBoard::random(&mut rng, 5, 5);
+            assert_eq!(board.validate(), Ok(()));
+            assert!(!board.is_forbidden_col(board.find_ball().unwrap()));
+        }
+
+        // A full side of a larger team is more than the standard team allows.
+        let team = TeamSpec {
+            attackers: 4,
+            defenders: 4,
+        };
+        let board = ChessBallBoard::random_with(&mut rng, &team, 8, 8);
+        assert_eq!(board.validate_with(&team), Ok(()));
+        assert_eq!(board.pieces().count(), 17);
+        assert!(board.validate().is_err());
+    }
+
+    #[test]
+    fn test_non_standard_team() {
+        let team = TeamSpec {
+            attackers: 3,
+            defenders: 4,
+        };
+        let board = ChessBallBoard::standard_start(&team);
+        assert_eq!(board.validate_with(&team), Ok(()));
+        assert_eq!(
+            board.validate(),
+            Err(BoardError::TooManyPieces {
+                player: Player::White,
+                piece_type: PieceType::Attacker,
+                count: 3,
+                max: 2,
+            })
+        );
+        assert_eq!(
+            ChessBallBoard::standard_start(&TeamSpec::STANDARD),
+            ChessBallBoard::new_game()
+        );
+
+        // Every attacker gets its moves, the third one included.
+        let moves = possible_moves(&board, Player::White);
+        let attackers: HashSet<Coord> = board
+            .pieces()
+            .filter(|(_, p)| p.player == Player::White && p.piece_type == PieceType::Attacker)
+            .map(|(at, _)| at)
+            .collect();
+        assert_eq!(attackers.len(), 3);
+        for at in &attackers {
+            assert!(moves.iter().any(|(mv, _)| mv.from == *at));
+        }
+
+        let text = board.to_string();
+        assert_eq!(ChessBallBoard::from_repr(&text), Ok(board.clone()));
+        assert_eq!(ChessBallBoard::from_repr_with(&text, &team), Ok(board));
+        assert!(ChessBallBoard::from_repr_with(&text, &TeamSpec::STANDARD).is_err());
+    }
+
+    #[test]
+    fn test_from_repr_strict_rejects_finished_games() {
+        let won = BoardBuilder::new()
+            .defender(Player::White, 4, 3)
+            .ball(5, 3)
+            .build()
+            .unwrap();
+        let text = won.to_string();
+        assert_eq!(ChessBallBoard::from_repr(&text), Ok(won));
+        let err = ChessBallBoard::from_repr_strict(&text).unwrap_err();
+        assert!(err.contains("White"), "{err}");
+
+        let start = ChessBallBoard::new_game().to_string();
+        assert_eq!(
+            ChessBallBoard::from_repr_strict(&start),
+            Ok(ChessBallBoard::new_game())
+        );
+    }
+
+    #[test]
+    fn test_from_repr_many_parses_each_block() {
+        let second = BoardBuilder::new()
+            .defender(Player::White, 4, 3)
+            .ball(3, 3)
+            .attacker(Player::Black, 1, 2)
+            .build()
+            .unwrap();
+        let text = format!("{}\n\n\n{}\n", ChessBallBoard::new_game(), second);
+        assert_eq!(
+            ChessBallBoard::from_repr_many(&text),
+            Ok(vec![ChessBallBoard::new_game(), second])
+        );
+
+        let broken = format!("{}\n\n-- WX --\n", ChessBallBoard::new_game());
+        let err = ChessBallBoard::from_repr_many(&broken).unwrap_err();
+        assert!(err.starts_with("position 2:"), "{err}");
+    }
+
+    #[test]
+    fn test_forbidden_cols_survive_the_text_format() {
+        let custom = BoardBuilder::new()
+            .forbidden_cols(&[0, 2, 6])
+            .ball(3, 3)
+            .build()
+            .unwrap();
+        let text = custom.to_string();
+        assert!(text.ends_with("forbidden: 0 2 6\n"), "{text}");
+        let parsed = ChessBallBoard::from_repr(&text).unwrap();
+        assert_eq!(parsed.forbidden_cols().collect::<Vec<_>>(), vec![0, 2, 6]);
+        assert_eq!(parsed, custom);
+
+        let mut open = custom.clone();
+        open.set_forbidden_cols([]);
+        let parsed = ChessBallBoard::from_repr(&open.to_string()).unwrap();
+        assert_eq!(parsed.forbidden_cols().count(), 0);
+
+        // The default mask is left implicit, so existing text stays valid.
+        let start = ChessBallBoard::new_game().to_string();
+        assert!(!start.contains("forbidden"));
+        assert!(ChessBallBoard::from_repr(&format!("{start}forbidden: 9")).is_err());
+    }
+
+    #[test]
+    fn test_board_width_is_checked() {
+        assert_eq!(ChessBallBoard::with_size(3, 0).forbidden_cols().count(), 0);
+        assert_eq!(
+            ChessBallBoard::with_size(3, 64)
+                .forbidden_cols()
+                .collect::<Vec<_>>(),
+            vec![0, 63]
+        );
+        assert_eq!(
+            ChessBallBoard::try_with_size(3, 65).err(),
+            Some(BoardError::TooWide(65))
+        );
+        assert_eq!(
+            BoardBuilder::new().size(3, 65).build().err(),
+            Some(BoardError::TooWide(65))
+        );
+        let wide_row = vec!["--"; 65].join(" ");
+        assert!(ChessBallBoard::from_repr(&wide_row).is_err());
+    }
+
+    #[test]
+    fn test_is_provably_dead() {
+        assert!(!ChessBallBoard::new_game().is_provably_dead());
+        assert!(ChessBallBoard::new().is_provably_dead());
+
+        // Every push from column 3 lands in columns 2..=4.
+        let frozen = BoardBuilder::new()
+            .forbidden_cols(&[2, 3, 4])
+            .defender(Player::White, 3, 3)
+            .ball(2, 3)
+            .build()
+            .unwrap();
+        assert!(frozen.is_provably_dead());
+        let mut open = frozen.clone();
+        open.set_forbidden_cols([2, 4]);
+        assert!(!open.is_provably_dead());
+
+        // Defenders that only move sideways can shove the ball along its row at most.
+        let sideways = MoveRules::default()
+            .with_directions(PieceType::Defender, [Direction::West, Direction::East]);
+        let mut level = BoardBuilder::new()
+            .move_rules(sideways)
+            .defender(Player::White, 2, 2)
+            .ball(2, 3)
+            .defender(Player::Black, 4, 4)
+            .build()
+            .unwrap();
+        assert!(level.is_provably_dead());
+        level.place_piece(
+            Coord { r: 5, c: 0 },
+            Piece {
+                piece_type: PieceType::Attacker,
+                player: Player::Black,
+            },
+        );
+        assert!(!level.is_provably_dead());
+    }
+
+    #[test]
+    fn test_transforms_map_asymmetric_move_rules() {
+        let north_only =
+            MoveRules::default().with_directions(PieceType::Attacker, [Direction::North]);
+        let east_only =
+            MoveRules::default().with_directions(PieceType::Attacker, [Direction::East]);
+        let board = |rules: MoveRules| {
+            BoardBuilder::new()
+                .move_rules(rules)
+                .attacker(Player::White, 3, 2)
+                .defender(Player::Black, 1, 5)
+                .ball(2, 4)
+                .build()
+                .unwrap()
+        };
+        let moves = |board: &ChessBallBoard, player: Player| -> HashSet<(Coord, Coord)> {
+            possible_moves(board, player)
+                .into_iter()
+                .map(|(mv, _)| (mv.from, mv.to))
+                .collect()
+        };
+        for rules in [north_only, east_only] {
+            let original = board(rules);
+            let (rows, cols) = (original.rows, original.cols);
+            let mirror = |at: Coord| Coord {
+                r: at.r,
+                c: cols - 1 - at.c,
+            };
+            let flip = |at: Coord| Coord {
+                r: rows - 1 - at.r,
+                c: at.c,
+            };
+            for player in [Player::White, Player::Black] {
+                let expected: HashSet<_> = moves(&original, player)
+                    .into_iter()
+                    .map(|(from, to)| (mirror(from), mirror(to)))
+                    .collect();
+                assert_eq!(moves(&original.mirror_horizontal(), player), expected);
+                let expected: HashSet<_> = moves(&original, player)
+                    .into_iter()
+                    .map(|(from, to)| (flip(from), flip(to)))
+                    .collect();
+                assert_eq!(
+                    moves(&original.flip_perspective(), player.opponent()),
+                    expected
+                );
+            }
+        }
+        assert!(
+            board(north_only)
+                .flip_perspective()
+                .move_rules()
+                .allows(PieceType::Attacker, Direction::South)
+        );
+    }
+
+    #[test]
+    fn test_algebraic_square_names() {
+        let at = |r, c| Coord { r, c };
+        assert_eq!(at(5, 0).algebraic(6), "a1");
+        assert_eq!(at(0, 6).algebraic(6), "g6");
+        // Past `z` the files continue as two letters.
+        assert_eq!(at(3, 25).algebraic(6), "z3");
+        assert_eq!(at(3, 26).algebraic(6), "aa3");
+        assert_eq!(at(0, 63).algebraic(10), "bl10");
+        for (name, r, c) in [("a1", 5, 0), ("G6", 0, 6), ("aa3", 3, 26), ("bl10", 0, 63)] {
+            let rows = if name == "bl10" { 10 } else { 6 };
+            assert_eq!(
+                Coord::from_algebraic(name, rows, 64),
+                Some(at(r, c)),
+                "{name}"
+            );
+        }
+        for name in ["", "a", "3", "a0", "a7", "h1", "1a", "a1b", "a-1"] {
+            assert_eq!(Coord::from_algebraic(name, 6, 7), None, "{name}");
+        }
+    }
 }
@@ -0,0 +1,301 @@
+//! Interactive game state: the current board, the side to move and an undo/redo history.
+
+use crate::board::{ChessBallBoard, Player};
+use crate::minimax::choose_best_move;
+use crate::moves::{MoveInfo, possible_moves};
+use crate::record::Termination;
+
+/// How an automatically played game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameResult {
+    /// The player brought the ball onto their goal row.
+    Win(Player),
+    /// The game stopped without a winner, for the given reason.
+    Draw(Termination),
+}
+
+/// A game in progress on the legacy board, with undo/redo support.
+///
+/// Every played move records the board it was played from, so `undo` can restore
+/// it exactly (including the tackle memory) and `redo` can re-apply it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameState {
+    pub board: ChessBallBoard,
+    pub to_move: Player,
+    /// Played moves with the board each was played from, oldest first.
+    history: Vec<(MoveInfo, ChessBallBoard)>,
+    /// Undone moves with the board each produced, most recently undone last.
+    redo: Vec<(MoveInfo, ChessBallBoard)>,
+}
+
+impl Default for GameState {
+    fn default() -> Self {
+        Self::new_game()
+    }
+}
+
+impl GameState {
+    /// Start a game from `board` with `to_move` to play and an empty history.
+    #[must_use]
+    pub fn new(board: ChessBallBoard, to_move: Player) -> Self {
+        Self {
+            board,
+            to_move,
+            history: Vec::new(),
+            redo: Vec::new(),
+        }
+    }
+
+    /// Start a game from the standard start position with White to play.
+    #[must_use]
+    pub fn new_game() -> Self {
+        Self::new(ChessBallBoard::new_game(), Player::White)
+    }
+
+    /// Record `mv` (which turned the current board into `board_after`) and pass the turn.
+    ///
+    /// Playing a move discards any redo history.
+    pub fn play(&mut self, mv: MoveInfo, board_after: ChessBallBoard) {
+        let board_before = std::mem::replace(&mut self.board, board_after);
+        self.history.push((mv, board_before));
+        self.redo.clear();
+        self.swap_player();
+    }
+
+    /// Take back the last move, restoring the previous board and side to move.
+    ///
+    /// Returns the undone move, or None if there is nothing to undo.
+    pub fn undo(&mut self) -> Option<MoveInfo> {
+        let (mv, board_before) = self.history.pop()?;
+        let board_after = std::mem::replace(&mut self.board, board_before);
+        self.redo.push((mv.clone(), board_after));
+        self.swap_player();
+        Some(mv)
+    }
+
+    /// Re-apply the most recently undone move.
+    ///
+    /// Returns the replayed move, or None if there is nothing to redo.
+    pub fn redo(&mut self) -> Option<MoveInfo> {
+        let (mv, board_after) = self.redo.pop()?;
+        let board_before = std::mem::replace(&mut self.board, board_after);
+        self.history.push((mv.clone(), board_before));
+        self.swap_player();
+        Some(mv)
+    }
+
+    /// Moves played so far, oldest first.
+    pub fn moves(&self) -> impl Iterator<Item = &MoveInfo> {
+        self.history.iter().map(|(mv, _)| mv)
+    }
+
+    /// The player whose goal row holds the ball, if any.
+    ///
+    /// As in `winning_moves`, Black wins on row 0 and White on the last row.
+    pub fn winner(&self) -> Option<Player> {
+        self.board.winner()
+    }
+
+    /// Let `choose_best_move` play both sides until a win, a draw or `max_plies` plies.
+    ///
+    /// The legacy-board self-play loop behind the CLI's `auto` command and, through
+    /// `auto_play_with`, `tournament::weights_tournament`. It follows the engine harness
+    /// in `tournament`: the game is drawn on the third occurrence of a position, when the
+    /// side to move has no legal move, when neither side can score any more
+    /// (`is_insufficient_material`), or at the ply limit. A game stopped by the limit on
+    /// a board that `is_provably_dead` is reported as a `DeadPosition` draw. `on_ply` is
+    /// called after every move with the move and the new state.
+    pub fn auto_play<F>(&mut self, depth: usize, max_plies: usize, on_ply: F) -> GameResult
+    where
+        F: FnMut(&MoveInfo, &GameState),
+    {
+        self.auto_play_with(
+            max_plies,
+            |board, player| choose_best_move(board, player, depth),
+            on_ply,
+        )
+    }
+
+    /// Like `auto_play`, but each move is picked by `choose`, which is given the board and
+    /// the side to move and answers like `choose_best_move`. If `choose` returns no move,
+    /// the first legal move is played.
+    pub fn auto_play_with<C, F>(
+        &mut self,
+        max_plies: usize,
+        mut choose: C,
+        mut on_ply: F,
+    ) -> GameResult
+    where
+        C: FnMut(&ChessBallBoard, Player) -> (Option<MoveInfo>, Option<ChessBallBoard>, f64),
+        F: FnMut(&MoveInfo, &GameState),
+    {
+        let mut plies = 0usize;
+        loop {
+            if let Some(winner) = self.winner() {
+                return GameResult::Win(winner);
+            }
+            if self.board.is_insufficient_material() {
+                return GameResult::Draw(Termination::InsufficientMaterial);
+            }
+            if plies >= max_plies {
+                return GameResult::Draw(self.limit_termination());
+            }
+            let (mv, board_after, _score) = choose(&self.board, self.to_move);
+            let Some((mv, board_after)) = mv
+                .zip(board_after)
+                .or_else(|| possible_moves(&self.board, self.to_move).into_iter().next())
+            else {
+                return GameResult::Draw(Termination::NoLegalMoves);
+            };
+            self.play(mv.clone(), board_after);
+            plies += 1;
+            on_ply(&mv, self);
+            if self.repetitions() >= 3 {
+                return GameResult::Draw(Termination::ThreefoldRepetition);
+            }
+        }
+    }
+
+    /// `PlyLimit`, or `DeadPosition` when the board `is_provably_dead`, so that no
+    /// number of further plies could have decided the game.
+    fn limit_termination(&self) -> Termination {
+        if self.board.is_provably_dead() {
+            Termination::DeadPosition
+        } else {
+            Termination::PlyLimit
+        }
+    }
+
+    /// How many times the current board has occurred with the same side to move.
+    fn repetitions(&self) -> usize {
+        1 + self
+            .history
+            .iter()
+            .rev()
+            .skip(1)
+            .step_by(2)
+            .filter(|(_, board)| *board == self.board)
+            .count()
+    }
+
+    fn swap_player(&mut self) {
+        self.to_move = self.to_move.opponent();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::{BoardBuilder, ChessBallBoard, Player};
+    use crate::game::{GameResult, GameState};
+    use crate::moves::possible_moves;
+    use crate::record::Termination;
+
+    #[test]
+    fn test_undo_then_redo_restores_state() {
+        let mut game = GameState::new_game();
+        for _ in 0..3 {
+            let (mv, board_after) = possible_moves(&game.board, game.to_move)
+                .into_iter()
+                .next()
+                .unwrap();
+            game.play(mv, board_after);
+        }
+        let after_three = game.clone();
+        assert_eq!(game.to_move, Player::Black);
+
+        let undone = game.undo().unwrap();
+        assert_eq!(game.to_move, Player::White);
+        assert_eq!(game.moves().count(), 2);
+        assert_ne!(game.board, after_three.board);
+
+        assert_eq!(game.redo(), Some(undone));
+        assert_eq!(game, after_three);
+        assert_eq!(game.redo(), None);
+    }
+
+    #[test]
+    fn test_undo_to_start_and_play_clears_redo() {
+        let start = GameState::new_game();
+        let mut game = start.clone();
+        let (mv, board_after) = possible_moves(&game.board, game.to_move)
+            .into_iter()
+            .next()
+            .unwrap();
+        game.play(mv.clone(), board_after.clone());
+        assert_eq!(game.undo(), Some(mv.clone()));
+        assert_eq!(game, {
+            let mut expected = start.clone();
+            expected.redo.push((mv.clone(), board_after.clone()));
+            expected
+        });
+        assert_eq!(game.undo(), None);
+
+        game.play(mv, board_after);
+        assert_eq!(game.redo(), None);
+    }
+
+    #[test]
+    fn test_new_game_starts_from_the_start_position() {
+        let game = GameState::new_game();
+        assert_eq!(game.board, ChessBallBoard::new_game());
+        assert_eq!(game.to_move, Player::White);
+        assert_eq!(game.moves().count(), 0);
+        assert_eq!(GameState::default(), game);
+    }
+
+    #[test]
+    fn test_auto_play_stops_on_win() {
+        // White can push the ball onto its goal row (the last row) right away.
+        let board = BoardBuilder::new()
+            .defender(Player::White, 3, 3)
+            .ball(4, 3)
+            .defender(Player::Black, 0, 0)
+            .build()
+            .unwrap();
+        let mut game = GameState::new(board, Player::White);
+        let mut played = Vec::new();
+        let result = game.auto_play(2, 50, |mv, _| played.push(mv.clone()));
+        assert_eq!(result, GameResult::Win(Player::White));
+        assert_eq!(played.len(), 1);
+        assert_eq!(game.winner(), Some(Player::White));
+        assert_eq!(game.to_move, Player::Black);
+    }
+
+    #[test]
+    fn test_auto_play_credits_the_mover_when_both_threaten() {
+        // On a three-row board both sides can push the ball home; only the side to move
+        // gets to.
+        let board = BoardBuilder::new()
+            .size(3, 7)
+            .defender(Player::White, 0, 3)
+            .ball(1, 3)
+            .defender(Player::Black, 2, 2)
+            .build()
+            .unwrap();
+        for mover in [Player::White, Player::Black] {
+            let mut game = GameState::new(board.clone(), mover);
+            assert_eq!(game.auto_play(2, 10, |_, _| {}), GameResult::Win(mover));
+            assert_eq!(game.moves().count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_auto_play_respects_ply_cap() {
+        let mut game = GameState::new_game();
+        // Nobody can score or repeat a position three times within four plies of the start.
+        let mut plies = 0;
+        let result = game.auto_play(1, 4, |_, _| plies += 1);
+        assert_eq!(result, GameResult::Draw(Termination::PlyLimit));
+        assert_eq!(plies, 4);
+        assert_eq!(game.moves().count(), 4);
+    }
+
+    #[test]
+    fn test_auto_play_draws_without_material() {
+        let board = BoardBuilder::new().ball(2, 3).build().unwrap();
+        let mut game = GameState::new(board, Player::White);
+        let result = game.auto_play(2, 50, |_, _| {});
+        assert_eq!(result, GameResult::Draw(Termination::InsufficientMaterial));
+        assert_eq!(game.moves().count(), 0);
+    }
+}
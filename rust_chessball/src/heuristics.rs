@@ -2,11 +2,14 @@
 //!
 //! Provides many of the same diagnostic features as the Python version.
 
-use crate::board::{ChessBallBoard, Coord, CoordDelta, DIRECTIONS, Piece, PieceType, Player};
-use crate::moves::possible_moves;
+use crate::board::{
+    ChessBallBoard, Coord, CoordDelta, DIRECTIONS, Piece, PieceType, Player, TeamSpec,
+};
+use crate::moves::{MoveInfo, MoveSpecialInfo, move_list};
 use crate::win_avoidability::is_win_avoidable_by_opponent;
-use crate::winning_moves::winning_moves;
+use crate::winning_moves::winning_moves_in;
 use std::collections::HashMap;
+use std::fmt;
 
 /// Return the ball position if present.
 pub fn ball_pos(board: &ChessBallBoard) -> Option<Coord> {
@@ -17,7 +20,7 @@ pub fn ball_pos(board: &ChessBallBoard) -> Option<Coord> {
 pub fn count_adjacent_pushers(board: &ChessBallBoard, player: Player) -> usize {
     if let Some(ball_coord) = board.find_ball() {
         let mut count = 0usize;
-        for &delta in DIRECTIONS.iter() {
+        for delta in board.directions() {
             if let Some(pusher_coord) = ball_coord - delta
                 && let Some(ball_destination) = ball_coord + delta
                 && !board.is_forbidden_col(ball_destination)
@@ -35,58 +38,117 @@ pub fn count_adjacent_pushers(board: &ChessBallBoard, player: Player) -> usize {
 
 /// Count friendly and enemy adjacent pieces around the ball.
 pub fn count_control_around_ball(board: &ChessBallBoard, player: Player) -> (usize, usize) {
-    if let Some(ball_coord) = board.find_ball() {
-        let mut friendly = 0usize;
-        let mut enemy = 0usize;
-        for &delta in DIRECTIONS.iter() {
-            if let Some(piece_coord) = ball_coord + delta {
-                match board.get_piece(piece_coord) {
-                    None => {}
-                    Some(Piece {
-                        piece_type: PieceType::Ball,
-                        player: _,
-                    }) => unreachable!("Two balls on board"),
-                    Some(Piece {
-                        piece_type: _,
-                        player: Player::Neutral,
-                    }) => unreachable!("Two balls on board"),
-                    Some(Piece {
-                        piece_type: _,
-                        player: piece_player,
-                    }) => {
-                        if *piece_player == player {
-                            friendly += 1
-                        } else {
-                            enemy += 1
-                        }
-                    }
+    let Some(ball_coord) = board.find_ball() else {
+        return (0, 0);
+    };
+    let mut friendly = 0usize;
+    let mut enemy = 0usize;
+    for (_, piece) in board.neighbors(ball_coord) {
+        match piece {
+            None => {}
+            Some(Piece {
+                piece_type: PieceType::Ball,
+                player: _,
+            }) => unreachable!("Two balls on board"),
+            Some(Piece {
+                piece_type: _,
+                player: Player::Neutral,
+            }) => unreachable!("Two balls on board"),
+            Some(Piece {
+                piece_type: _,
+                player: piece_player,
+            }) => {
+                if *piece_player == player {
+                    friendly += 1
+                } else {
+                    enemy += 1
                 }
             }
         }
-        return (friendly, enemy);
     }
-    (0, 0)
+    (friendly, enemy)
 }
 
 /// Number of legal moves for player.
 pub fn mobility(board: &ChessBallBoard, player: Player) -> usize {
-    possible_moves(board, player).len()
+    move_list(board, player).len()
+}
+
+/// Count `player`'s pieces on their own goal row (where the opponent wins) and the row
+/// in front of it, the squares that stop a losing push.
+pub fn goal_defense(board: &ChessBallBoard, player: Player) -> usize {
+    let Some(own_goal) = player.opponent().goal_row(board.rows) else {
+        return 0;
+    };
+    board
+        .iter_coords()
+        .filter(|coord| coord.r.abs_diff(own_goal) <= 1)
+        .filter(|&coord| {
+            board
+                .get_piece(coord)
+                .is_some_and(|p| p.player == player && p.piece_type != PieceType::Ball)
+        })
+        .count()
+}
+
+/// Count the legal jumps of `player`'s attackers.
+pub fn attacker_jump_options(board: &ChessBallBoard, player: Player) -> usize {
+    count_jumps(&move_list(board, player))
+}
+
+fn count_jumps(moves: &[MoveInfo]) -> usize {
+    moves
+        .iter()
+        .filter(|mv| matches!(mv.special, MoveSpecialInfo::AttackerJump { .. }))
+        .count()
+}
+
+/// Count the distinct squares `player` could push the ball to this move.
+pub fn ball_reach(board: &ChessBallBoard, player: Player) -> usize {
+    ball_reach_in(&move_list(board, player))
+}
+
+fn ball_reach_in(moves: &[MoveInfo]) -> usize {
+    let mut targets: Vec<Coord> = moves
+        .iter()
+        .filter_map(|mv| match mv.special {
+            MoveSpecialInfo::BallPush { ball_to } => Some(ball_to),
+            _ => None,
+        })
+        .collect();
+    targets.sort();
+    targets.dedup();
+    targets.len()
+}
+
+/// Count `player`'s pieces (the ball excluded) that have no legal move.
+pub fn trapped_pieces(board: &ChessBallBoard, player: Player) -> usize {
+    trapped_pieces_in(board, player, &move_list(board, player))
+}
+
+/// `trapped_pieces` given `player`'s move list.
+fn trapped_pieces_in(board: &ChessBallBoard, player: Player, moves: &[MoveInfo]) -> usize {
+    board
+        .iter_coords()
+        .filter(|&coord| {
+            board
+                .get_piece(coord)
+                .is_some_and(|p| p.player == player && p.piece_type != PieceType::Ball)
+                && !moves.iter().any(|mv| mv.from == coord)
+        })
+        .count()
 }
 
 /// Count pieces of `player` that are vulnerable to being tackled.
 pub fn vulnerable_pieces_count(board: &ChessBallBoard, player: Player) -> usize {
-    let opponent = match player {
-        Player::White => Player::Black,
-        Player::Black => Player::White,
-        Player::Neutral => Player::Neutral,
-    };
+    let opponent = player.opponent();
     let mut vuln = 0usize;
     for coord in board.iter_coords() {
         if let Some(p) = board.get_piece(coord) {
             if p.player != player {
                 continue;
             }
-            for &delta in crate::board::DIRECTIONS.iter() {
+            for delta in board.directions() {
                 if let Some(opp_coord) = coord + delta
                     && let Some(destination) = coord - delta
                     && board.get_piece(destination).is_some()
@@ -96,10 +158,8 @@ pub fn vulnerable_pieces_count(board: &ChessBallBoard, player: Player) -> usize
                         Some(&Piece {
                             piece_type: PieceType::Defender,
                             player: p,
-                        }) => {
-                            if p == opponent {
-                                vuln += 1;
-                            }
+                        }) if p == opponent => {
+                            vuln += 1;
                         }
                         Some(&Piece {
                             piece_type: _,
@@ -113,13 +173,81 @@ pub fn vulnerable_pieces_count(board: &ChessBallBoard, player: Player) -> usize
     vuln
 }
 
+/// Count distinct opponent pieces that one of `player`'s defenders could tackle next move.
+///
+/// Follows the tackle rule of `possible_moves`: the target sits next to the defender, the
+/// square beyond it is empty, and the piece that just tackled us cannot be tackled back.
+pub fn opponent_vulnerable_pieces(board: &ChessBallBoard, player: Player) -> usize {
+    let mut targets: Vec<Coord> = Vec::new();
+    for coord in board.iter_coords() {
+        let Some(p) = board.get_piece(coord) else {
+            continue;
+        };
+        if p.player != player || p.piece_type != PieceType::Defender {
+            continue;
+        }
+        for delta in board.directions() {
+            if let Some(target) = coord + delta
+                && let Some(beyond) = target + delta
+                && let Some(victim) = board.get_piece(target)
+                && victim.player != player
+                && victim.piece_type != PieceType::Ball
+                && board.get_piece(beyond).is_none()
+                && !board.prev_tackle.as_ref().is_some_and(|tackle| {
+                    (tackle.pushed_piece_from, tackle.pushed_piece_to) == (target, coord)
+                })
+                && !targets.contains(&target)
+            {
+                targets.push(target);
+            }
+        }
+    }
+    targets.len()
+}
+
+/// How firmly `player` pins the ball against a forbidden column, in [0,1].
+///
+/// The ball is pinned when it sits in column 1 or `cols - 2`, next to a forbidden column.
+/// It can then only be pushed back towards the center by a piece standing in the
+/// forbidden column on one of the (up to three) squares adjacent to the ball. The feature
+/// is the fraction of those on-board squares occupied by `player`'s pieces: 1.0 when the
+/// player holds all of them, so the opponent has no way to reroute the ball, and 0.0 when
+/// the ball is not next to a forbidden column.
+pub fn ball_against_forbidden(board: &ChessBallBoard, player: Player) -> f64 {
+    let Some(ball_coord) = board.find_ball() else {
+        return 0.0;
+    };
+    if board.cols < 3 || board.is_forbidden_col(ball_coord) {
+        return 0.0;
+    }
+    let wall_col = if ball_coord.c == 1 {
+        0
+    } else if ball_coord.c == board.cols - 2 {
+        board.cols - 1
+    } else {
+        return 0.0;
+    };
+    let mut squares = 0usize;
+    let mut held = 0usize;
+    for r in ball_coord.r.saturating_sub(1)..=(ball_coord.r + 1).min(board.rows - 1) {
+        squares += 1;
+        if board
+            .get_piece(Coord { r, c: wall_col })
+            .is_some_and(|p| p.player == player)
+        {
+            held += 1;
+        }
+    }
+    held as f64 / squares as f64
+}
+
 /// Cheap approximation for push distance to goal (normalized).
 pub fn approx_push_distance(board: &ChessBallBoard, player: Player) -> f64 {
     if let Some(ball_coord) = board.find_ball() {
         let dist = match player {
             Player::White => (board.rows - 1) as isize - ball_coord.r as isize,
             Player::Black => ball_coord.r as isize,
-            Player::Neutral => (board.rows - 1) as isize - ball_coord.r as isize,
+            Player::Neutral => return 0.0,
         } as f64;
         let max_dist = (board.rows - 1) as f64;
         if max_dist == 0.0 {
@@ -168,7 +296,7 @@ pub fn ball_row_for_player(board: &ChessBallBoard, player: Player) -> f64 {
         let val = match player {
             Player::White => ball_coord.r as f64,
             Player::Black => (board.rows as isize - 1 - ball_coord.r as isize) as f64,
-            Player::Neutral => ball_coord.r as f64,
+            Player::Neutral => return 0.0,
         };
         return val / ((board.rows - 1) as f64);
     }
@@ -179,6 +307,22 @@ pub fn ball_row_for_player(board: &ChessBallBoard, player: Player) -> f64 {
 pub fn count_opponent_pieces_between_ball_and_goal(
     board: &ChessBallBoard,
     player: Player,
+) -> usize {
+    count_pieces_between_ball_and_goal(board, player, |owner| owner != player)
+}
+
+/// Count `player`'s own pieces strictly between ball row and goal row: friendly pieces
+/// in the way of their own pushes.
+pub fn count_own_pieces_between_ball_and_goal(board: &ChessBallBoard, player: Player) -> usize {
+    count_pieces_between_ball_and_goal(board, player, |owner| owner == player)
+}
+
+/// Count the non-ball pieces whose owner passes `owned` strictly between the ball's row
+/// and `player`'s goal row.
+fn count_pieces_between_ball_and_goal(
+    board: &ChessBallBoard,
+    player: Player,
+    owned: impl Fn(Player) -> bool,
 ) -> usize {
     if let Some(ball_coord) = board.find_ball() {
         if player == Player::Neutral {
@@ -194,34 +338,717 @@ pub fn count_opponent_pieces_between_ball_and_goal(
         if end - start <= 1 {
             return 0;
         }
-        let mut count = 0usize;
-        for coord in board.iter_coords() {
-            if coord.r as isize > start
-                && let Some(p) = board.get_piece(coord)
-                && p.player != player
-                && p.piece_type != PieceType::Ball
+        return (start + 1..end)
+            .flat_map(|r| board.row(r as usize).iter().flatten())
+            .filter(|p| owned(p.player) && p.piece_type != PieceType::Ball)
+            .count();
+    }
+    0
+}
+
+/// Size of the group of `player`'s pieces connected to the ball: a flood fill through
+/// 8-adjacency among friendly pieces, starting from those next to the ball.
+pub fn ball_cluster_size(board: &ChessBallBoard, player: Player) -> usize {
+    let Some(ball_coord) = board.find_ball() else {
+        return 0;
+    };
+    let is_friendly = |piece: Option<&Piece>| piece.is_some_and(|p| p.player == player);
+    let mut cluster: Vec<Coord> = board
+        .neighbors(ball_coord)
+        .filter(|&(_, piece)| is_friendly(piece))
+        .map(|(coord, _)| coord)
+        .collect();
+    let mut next = 0;
+    while next < cluster.len() {
+        for (coord, piece) in board.neighbors(cluster[next]) {
+            if is_friendly(piece) && !cluster.contains(&coord) {
+                cluster.push(coord);
+            }
+        }
+        next += 1;
+    }
+    cluster.len()
+}
+
+/// Chebyshev (king-move) distance from the ball to the nearest square of `player`'s goal
+/// row the ball may enter: the larger of the row distance to the goal row and the column
+/// distance to the nearest column that is not forbidden. Normalized by `rows - 1` to
+/// [0,1], 1 meaning the ball is on an enterable goal square; farther than `rows - 1`
+/// scores 0, as does a board without a ball or without an enterable column.
+pub fn ball_goal_chebyshev(board: &ChessBallBoard, player: Player) -> f64 {
+    let (Some(ball), Some(goal_row)) = (board.find_ball(), player.goal_row(board.rows)) else {
+        return 0.0;
+    };
+    let Some(col_dist) = (0..board.cols)
+        .filter(|&c| !board.is_forbidden_col(Coord { r: goal_row, c }))
+        .map(|c| c.abs_diff(ball.c))
+        .min()
+    else {
+        return 0.0;
+    };
+    let dist = ball.r.abs_diff(goal_row).max(col_dist);
+    let max_dist = board.rows - 1;
+    if max_dist == 0 {
+        return if dist == 0 { 1.0 } else { 0.0 };
+    }
+    1.0 - dist.min(max_dist) as f64 / max_dist as f64
+}
+
+/// Count `player`'s pieces lined up directly behind the ball, opposite to the player's
+/// forward direction, stopping at the first square that is empty or not friendly.
+pub fn pusher_chain_length(board: &ChessBallBoard, player: Player) -> usize {
+    let Some(ball_coord) = board.find_ball() else {
+        return 0;
+    };
+    let backward = CoordDelta {
+        r: if player == Player::Black { 1 } else { -1 },
+        c: 0,
+        rows: board.rows,
+        cols: board.cols,
+    };
+    let mut count = 0usize;
+    let mut at = ball_coord + backward;
+    while let Some(coord) = at
+        && let Some(p) = board.get_piece(coord)
+        && p.player == player
+    {
+        count += 1;
+        at = coord + backward;
+    }
+    count
+}
+
+/// Chebyshev (king-move) distance from the ball to the nearest piece of `player`, or None
+/// without a ball or without pieces. 1 means a piece stands next to the ball.
+pub fn nearest_pusher_distance(board: &ChessBallBoard, player: Player) -> Option<usize> {
+    let ball = board.find_ball()?;
+    board
+        .pieces()
+        .filter(|(_, piece)| piece.player == player && piece.piece_type != PieceType::Ball)
+        .map(|(at, _)| at.r.abs_diff(ball.r).max(at.c.abs_diff(ball.c)))
+        .min()
+}
+
+/// `nearest_pusher_distance` scaled to [0,1]: 1 with a piece next to the ball, falling
+/// to 0 at the largest distance the board allows, and 0 without any piece.
+fn pusher_proximity(board: &ChessBallBoard, player: Player) -> f64 {
+    let Some(distance) = nearest_pusher_distance(board, player) else {
+        return 0.0;
+    };
+    let max_dist = board.rows.max(board.cols) - 1;
+    if max_dist <= 1 {
+        return 1.0;
+    }
+    1.0 - (distance - 1) as f64 / (max_dist - 1) as f64
+}
+
+/// `pusher_chain_length` capped at the ball's distance to `player`'s goal row: each push
+/// moves the ball one row forward, so pieces beyond that many have nothing left to push.
+pub fn forward_momentum(board: &ChessBallBoard, player: Player) -> usize {
+    let (Some(ball), Some(goal_row)) = (board.find_ball(), player.goal_row(board.rows)) else {
+        return 0;
+    };
+    pusher_chain_length(board, player).min(ball.r.abs_diff(goal_row))
+}
+
+/// A per-square bonus table for one piece type, laid out for the default board size.
+///
+/// Tables are written from White's side: row `r` is board row `r`, and White advances
+/// towards the last row. Black reads them with the rows mirrored.
+pub type PieceSquareTable = [[f64; ChessBallBoard::DEFAULT_COLS]; ChessBallBoard::DEFAULT_ROWS];
+
+/// Piece-square tables used by `positional_score`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PieceSquareTables {
+    pub attacker: PieceSquareTable,
+    pub defender: PieceSquareTable,
+    pub ball: PieceSquareTable,
+}
+
+impl PieceSquareTables {
+    /// Default tables, in [0,1], favoring central columns and advanced rows. Defenders
+    /// weigh the center more and advancement less than attackers and the ball.
+    pub const DEFAULT: Self = Self {
+        attacker: default_table(0.5),
+        defender: default_table(0.25),
+        ball: default_table(0.5),
+    };
+
+    fn table(&self, piece_type: PieceType) -> &PieceSquareTable {
+        match piece_type {
+            PieceType::Attacker => &self.attacker,
+            PieceType::Defender => &self.defender,
+            PieceType::Ball => &self.ball,
+        }
+    }
+}
+
+impl Default for PieceSquareTables {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Table worth `advance * row_progress + (1 - advance) * centrality` on each square.
+const fn default_table(advance: f64) -> PieceSquareTable {
+    const ROWS: usize = ChessBallBoard::DEFAULT_ROWS;
+    const COLS: usize = ChessBallBoard::DEFAULT_COLS;
+    let mut table = [[0.0; COLS]; ROWS];
+    let mut r = 0;
+    while r < ROWS {
+        let mut c = 0;
+        while c < COLS {
+            let progress = r as f64 / (ROWS - 1) as f64;
+            let centrality = 1.0 - c.abs_diff(COLS / 2) as f64 / (COLS / 2) as f64;
+            table[r][c] = advance * progress + (1.0 - advance) * centrality;
+            c += 1;
+        }
+        r += 1;
+    }
+    table
+}
+
+/// Positional score of `player`'s pieces and the ball under the default tables, in [0,1].
+pub fn positional_score(board: &ChessBallBoard, player: Player) -> f64 {
+    positional_score_with(board, player, &PieceSquareTables::DEFAULT)
+}
+
+/// Sum the table values of `player`'s pieces and of the ball, normalized by the largest
+/// piece count of the standard team (`TeamSpec::STANDARD`). Squares outside the tables
+/// (non-default boards) score 0.
+pub fn positional_score_with(
+    board: &ChessBallBoard,
+    player: Player,
+    tables: &PieceSquareTables,
+) -> f64 {
+    let mut total = 0.0;
+    for coord in board.iter_coords() {
+        if let Some(p) = board.get_piece(coord)
+            && (p.player == player || p.piece_type == PieceType::Ball)
+        {
+            let row = if player == Player::Black {
+                board.rows - 1 - coord.r
+            } else {
+                coord.r
+            };
+            if let Some(value) = tables
+                .table(p.piece_type)
+                .get(row)
+                .and_then(|r| r.get(coord.c))
             {
-                count += 1;
+                total += value;
             }
         }
-        return count;
     }
-    0
+    total / (ChessBallBoard::MAX_ATTACKERS + ChessBallBoard::MAX_DEFENDERS + 1) as f64
 }
 
-/// Feature vector similar to the Python implementation.
-/// Returns a HashMap mapping feature name to value.
-pub fn feature_vector(board: &ChessBallBoard, player: Player) -> HashMap<String, f64> {
-    let opponent = match player {
-        Player::White => Player::Black,
-        Player::Black => Player::White,
-        Player::Neutral => Player::Neutral,
+/// Material value of each piece type, used by `material_balance`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PieceValues {
+    pub attacker: f64,
+    pub defender: f64,
+}
+
+impl PieceValues {
+    /// Attackers, which can jump, are worth more than defenders.
+    pub const DEFAULT: Self = Self {
+        attacker: 1.5,
+        defender: 1.0,
+    };
+
+    /// Value of one piece of `piece_type`; the ball is worth nothing.
+    pub fn value(&self, piece_type: PieceType) -> f64 {
+        match piece_type {
+            PieceType::Attacker => self.attacker,
+            PieceType::Defender => self.defender,
+            PieceType::Ball => 0.0,
+        }
+    }
+}
+
+impl Default for PieceValues {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Default material value of a piece type.
+pub fn piece_value(piece_type: PieceType) -> f64 {
+    PieceValues::DEFAULT.value(piece_type)
+}
+
+/// Material balance of `player` under the default piece values, in [-1,1].
+pub fn material_balance(board: &ChessBallBoard, player: Player) -> f64 {
+    material_balance_with(board, player, &PieceValues::DEFAULT)
+}
+
+/// Difference between the summed piece values of `player` and of the opponent, divided
+/// by the value of a full side of the standard team (`TeamSpec::STANDARD`).
+pub fn material_balance_with(board: &ChessBallBoard, player: Player, values: &PieceValues) -> f64 {
+    let mut balance = 0.0;
+    for coord in board.iter_coords() {
+        if let Some(p) = board.get_piece(coord)
+            && p.player != Player::Neutral
+        {
+            let value = values.value(p.piece_type);
+            if p.player == player {
+                balance += value;
+            } else {
+                balance -= value;
+            }
+        }
+    }
+    let full_side = ChessBallBoard::MAX_ATTACKERS as f64 * values.attacker
+        + ChessBallBoard::MAX_DEFENDERS as f64 * values.defender;
+    if full_side == 0.0 {
+        return 0.0;
+    }
+    balance / full_side
+}
+
+/// Normalizers that scale raw counts into the feature ranges.
+///
+/// The bounds follow from the rules and the team size rather than from the board size,
+/// so features stay within [-1,1] on boards of any dimensions. `DEFAULT` covers the
+/// standard team; use `for_team` for other teams. `positional` and `material_balance`
+/// are scaled by their own tables and stay normalized by the standard team.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeatureConfig {
+    /// Most pieces one side may have (ball excluded). Scales the per-side piece counts:
+    /// `vulnerable`, `opponent_vulnerable_pieces`, `pusher_chain_length`,
+    /// `trapped_pieces` and `opp_between_ball_and_goal`.
+    pub max_pieces: f64,
+    /// Most attackers one side may have. Scales `attacker_jump_options`.
+    pub max_attackers: f64,
+    /// Number of squares around the ball. Scales `adj_pushers`, `opp_adj_pushers` and
+    /// `control`.
+    pub neighbourhood: f64,
+    /// Largest possible difference between the two sides' move counts. Scales `mobility`.
+    pub mobility_cap: f64,
+}
+
+impl FeatureConfig {
+    /// `for_team` for the standard team.
+    pub const DEFAULT: Self = Self::for_team(&TeamSpec::STANDARD);
+
+    /// Bounds for sides of `team`: every piece makes at most one move per direction.
+    #[must_use]
+    pub const fn for_team(team: &TeamSpec) -> Self {
+        let max_pieces = (team.attackers + team.defenders) as f64;
+        let neighbourhood = DIRECTIONS.len() as f64;
+        Self {
+            max_pieces,
+            max_attackers: team.attackers as f64,
+            neighbourhood,
+            mobility_cap: max_pieces * neighbourhood,
+        }
+    }
+}
+
+impl Default for FeatureConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Heuristic features of a position from one player's point of view.
+///
+/// One named field per feature; `as_array` and `iter` list them in `Features::NAMES` order.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Features {
+    pub win_now: f64,
+    pub lose_now: f64,
+    pub ball_row: f64,
+    pub ball_in_forbidden_col: f64,
+    pub adj_pushers: f64,
+    pub opp_adj_pushers: f64,
+    pub control: f64,
+    pub mobility: f64,
+    pub push_distance: f64,
+    pub unavoidable_win: f64,
+    pub vulnerable: f64,
+    pub ball_row_value: f64,
+    pub opp_between_ball_and_goal: f64,
+    pub ball_goal_chebyshev: f64,
+    pub pusher_chain_length: f64,
+    pub opponent_vulnerable_pieces: f64,
+    pub positional: f64,
+    pub ball_against_forbidden: f64,
+    pub material_balance: f64,
+    pub trapped_pieces: f64,
+    pub ball_cluster: f64,
+    pub side_to_move: f64,
+    pub attacker_jump_options: f64,
+    pub goal_defense: f64,
+    pub forward_momentum: f64,
+    pub ball_reach: f64,
+    /// Pieces of both sides between the ball and `player`'s goal, out of both sides'
+    /// `max_pieces`.
+    pub congestion: f64,
+    /// `nearest_pusher_distance` mapped to [0,1], 1 with a piece next to the ball.
+    pub pusher_proximity: f64,
+}
+
+impl Features {
+    /// Number of features.
+    pub const COUNT: usize = 28;
+
+    /// Feature names, in the order used by `as_array` and `iter`.
+    pub const NAMES: [&'static str; Self::COUNT] = [
+        "win_now",
+        "lose_now",
+        "ball_row",
+        "ball_in_forbidden_col",
+        "adj_pushers",
+        "opp_adj_pushers",
+        "control",
+        "mobility",
+        "push_distance",
+        "unavoidable_win",
+        "vulnerable",
+        "ball_row_value",
+        "opp_between_ball_and_goal",
+        "ball_goal_chebyshev",
+        "pusher_chain_length",
+        "opponent_vulnerable_pieces",
+        "positional",
+        "ball_against_forbidden",
+        "material_balance",
+        "trapped_pieces",
+        "ball_cluster",
+        "side_to_move",
+        "attacker_jump_options",
+        "goal_defense",
+        "forward_momentum",
+        "ball_reach",
+        "congestion",
+        "pusher_proximity",
+    ];
+
+    /// Feature values, in `Features::NAMES` order.
+    pub fn as_array(&self) -> [f64; Self::COUNT] {
+        [
+            self.win_now,
+            self.lose_now,
+            self.ball_row,
+            self.ball_in_forbidden_col,
+            self.adj_pushers,
+            self.opp_adj_pushers,
+            self.control,
+            self.mobility,
+            self.push_distance,
+            self.unavoidable_win,
+            self.vulnerable,
+            self.ball_row_value,
+            self.opp_between_ball_and_goal,
+            self.ball_goal_chebyshev,
+            self.pusher_chain_length,
+            self.opponent_vulnerable_pieces,
+            self.positional,
+            self.ball_against_forbidden,
+            self.material_balance,
+            self.trapped_pieces,
+            self.ball_cluster,
+            self.side_to_move,
+            self.attacker_jump_options,
+            self.goal_defense,
+            self.forward_momentum,
+            self.ball_reach,
+            self.congestion,
+            self.pusher_proximity,
+        ]
+    }
+
+    /// `(name, value)` pairs, in `Features::NAMES` order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, f64)> {
+        Self::NAMES.into_iter().zip(self.as_array())
+    }
+
+    /// Sum of all feature values.
+    pub fn sum(&self) -> f64 {
+        self.as_array().iter().sum()
+    }
+
+    /// One line per feature, in `Features::NAMES` order, with its value and its
+    /// contribution to `evaluate_with` under `weights`, followed by the total.
+    pub fn pretty(&self, weights: &HeuristicWeights) -> String {
+        let width = Self::NAMES.iter().map(|name| name.len()).max().unwrap_or(0);
+        let mut out = String::new();
+        let mut total = 0.0;
+        for ((name, value), weight) in self.iter().zip(weights.features) {
+            let contribution = value * weight;
+            total += contribution;
+            out.push_str(&format!(
+                "{name:<width$} {value:>8.4} {contribution:>8.4}\n"
+            ));
+        }
+        out.push_str(&format!("{:<width$} {:>8} {total:>8.4}", "total", ""));
+        out
+    }
+
+    /// Features kept up to date by `update_incremental`. They only depend on the ball's
+    /// square and its immediate neighbourhood.
+    pub const INCREMENTAL: [&'static str; 8] = [
+        "ball_row",
+        "ball_in_forbidden_col",
+        "adj_pushers",
+        "opp_adj_pushers",
+        "control",
+        "ball_row_value",
+        "ball_goal_chebyshev",
+        "ball_against_forbidden",
+    ];
+
+    /// Features measuring `player`'s exposure, summed into the defense component of
+    /// `evaluate_components`. Every other feature counts towards offense.
+    pub const DEFENSIVE: [&'static str; 9] = [
+        "lose_now",
+        "ball_in_forbidden_col",
+        "opp_adj_pushers",
+        "vulnerable",
+        "opp_between_ball_and_goal",
+        "ball_against_forbidden",
+        "material_balance",
+        "trapped_pieces",
+        "goal_defense",
+    ];
+
+    /// Update the cheap, ball-local features (`Features::INCREMENTAL`) after `mv` turned
+    /// the position these features were computed on, with `features_with(.., weights)`,
+    /// into `board_after`.
+    ///
+    /// Nothing is recomputed when the move leaves the ball and its neighbouring squares
+    /// alone. Every other feature (win detection, mobility, vulnerability, pusher chains,
+    /// positional tables, ...) is left untouched and needs a full `features` recompute.
+    ///
+    /// The minimax search does not use this: a leaf's score needs those other features
+    /// too, so it recomputes everything. It is public for callers that only follow the
+    /// ball-local features along a line of play and want to skip the move generation a
+    /// full recompute does on every step.
+    pub fn update_incremental(
+        &mut self,
+        board_after: &ChessBallBoard,
+        mv: &MoveInfo,
+        player: Player,
+        weights: &HeuristicWeights,
+    ) {
+        let Some(ball) = board_after.find_ball() else {
+            *self = Features {
+                ball_row: 0.0,
+                ball_in_forbidden_col: 0.0,
+                adj_pushers: 0.0,
+                opp_adj_pushers: 0.0,
+                control: 0.0,
+                ball_row_value: ball_row_for_player(board_after, player),
+                ball_goal_chebyshev: 0.0,
+                ball_against_forbidden: 0.0,
+                ..*self
+            };
+            return;
+        };
+        let near_ball = |at: Coord| at.r.abs_diff(ball.r) <= 1 && at.c.abs_diff(ball.c) <= 1;
+        let ball_moved = matches!(mv.special, MoveSpecialInfo::BallPush { .. });
+        let shoved_near_ball = match &mv.special {
+            MoveSpecialInfo::DefenderTackle(tackle) => tackle.shoved().any(|(_, to)| near_ball(to)),
+            _ => false,
+        };
+        if ball_moved {
+            (self.ball_row, self.ball_in_forbidden_col) = ball_row_features(board_after, player);
+            self.ball_row_value = ball_row_for_player(board_after, player);
+            self.ball_goal_chebyshev = ball_goal_chebyshev(board_after, player);
+        }
+        if ball_moved || near_ball(mv.from) || near_ball(mv.to) || shoved_near_ball {
+            (self.adj_pushers, self.opp_adj_pushers, self.control) =
+                ball_neighbourhood_features(board_after, player, &weights.config);
+            self.ball_against_forbidden = ball_against_forbidden(board_after, player);
+        }
+    }
+
+    /// The features as a map from name to value, the shape returned by `feature_vector`.
+    pub fn to_map(&self) -> HashMap<String, f64> {
+        self.iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect()
+    }
+}
+
+/// The `side_to_move` feature: the mover's tempo bonus, larger when the mover threatens
+/// to win, counted for `player` when they are to move and against them otherwise.
+fn tempo(player: Player, to_move: Player, player_wins: bool, opp_wins: bool) -> f64 {
+    let bonus = |threatens: bool| {
+        if threatens {
+            THREAT_TEMPO_BONUS
+        } else {
+            TEMPO_BONUS
+        }
+    };
+    if to_move == player {
+        bonus(player_wins)
+    } else if to_move == player.opponent() {
+        -bonus(opp_wins)
+    } else {
+        0.0
+    }
+}
+
+/// `side_to_move` bonus for having the move in a quiet position.
+const TEMPO_BONUS: f64 = 0.1;
+
+/// `side_to_move` bonus for having the move while threatening a win, when the tempo
+/// decides the game.
+const THREAT_TEMPO_BONUS: f64 = 0.5;
+
+impl fmt::Display for Features {
+    /// `pretty` with unit weights, so contributions add up to `evaluate`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.pretty(&HeuristicWeights::DEFAULT))
+    }
+}
+
+/// Compute the heuristic features of `board` for `player`, who is the side to move.
+pub fn features(board: &ChessBallBoard, player: Player) -> Features {
+    features_with(board, player, &HeuristicWeights::DEFAULT)
+}
+
+/// Compute the heuristic features, scoring `positional` and `material_balance` with the
+/// tables and piece values of `weights` and normalizing with `weights.config`.
+///
+/// `Neutral` is not a side: all its features are 0.
+pub fn features_with(
+    board: &ChessBallBoard,
+    player: Player,
+    weights: &HeuristicWeights,
+) -> Features {
+    features_for(board, player, player, weights)
+}
+
+/// `features_with` for `player` in a position where `to_move` has the move, which only
+/// changes `side_to_move`: the tempo bonus counts for `player` when they are to move and
+/// against them when the opponent is.
+pub fn features_for(
+    board: &ChessBallBoard,
+    player: Player,
+    to_move: Player,
+    weights: &HeuristicWeights,
+) -> Features {
+    if player == Player::Neutral {
+        return Features::default();
+    }
+    let own = SideMoves::new(board, player);
+    let opp = SideMoves::new(board, player.opponent());
+    features_from(board, player, to_move, weights, &own, &opp)
+}
+
+/// A side's move list and whether one of the moves wins: the move generation shared by
+/// the features of both sides.
+struct SideMoves {
+    moves: Vec<MoveInfo>,
+    wins: bool,
+}
+
+impl SideMoves {
+    fn new(board: &ChessBallBoard, player: Player) -> Self {
+        let moves = move_list(board, player);
+        let wins = !winning_moves_in(board, player, &moves).is_empty();
+        Self { moves, wins }
+    }
+}
+
+/// `features_for` for a side that is not `Neutral`, given both sides' moves.
+fn features_from(
+    board: &ChessBallBoard,
+    player: Player,
+    to_move: Player,
+    weights: &HeuristicWeights,
+    own: &SideMoves,
+    opp: &SideMoves,
+) -> Features {
+    let player_wins = own.wins;
+    let opp_wins = opp.wins;
+
+    let (ball_row_feature, ball_in_forbidden) = ball_row_features(board, player);
+    let (adj_pushers, opp_adj_pushers, control) =
+        ball_neighbourhood_features(board, player, &weights.config);
+
+    let mob_p = own.moves.len() as f64;
+    let mob_o = opp.moves.len() as f64;
+    let FeatureConfig {
+        max_pieces,
+        max_attackers,
+        neighbourhood,
+        mobility_cap,
+    } = weights.config;
+    let mob = (mob_p - mob_o) / mobility_cap;
+
+    let vulnerable = vulnerable_pieces_count(board, player) as f64 / max_pieces;
+    let opp_vulnerable = opponent_vulnerable_pieces(board, player) as f64 / max_pieces;
+
+    let push_dist = approx_push_distance(board, player);
+
+    let mut unavoidable = 0.0;
+    if player_wins && !is_win_avoidable_by_opponent(board, player) {
+        unavoidable = 1.0;
+    }
+
+    let ball_row_value = ball_row_for_player(board, player);
+    let opp_between =
+        count_opponent_pieces_between_ball_and_goal(board, player) as f64 / max_pieces;
+    let goal_chebyshev = ball_goal_chebyshev(board, player);
+    let pusher_chain = pusher_chain_length(board, player) as f64 / max_pieces;
+    let own_pieces = board
+        .iter_coords()
+        .filter(|&coord| board.get_piece(coord).is_some_and(|p| p.player == player))
+        .count();
+    let forward = match (board.find_ball(), player.goal_row(board.rows)) {
+        (Some(ball), Some(goal_row)) if ball.r != goal_row => {
+            forward_momentum(board, player) as f64 / ball.r.abs_diff(goal_row) as f64
+        }
+        _ => 0.0,
+    };
+    let ball_cluster = if own_pieces == 0 {
+        0.0
+    } else {
+        ball_cluster_size(board, player) as f64 / own_pieces as f64
     };
 
-    let player_wins = !winning_moves(board, player).is_empty();
-    let opp_wins = !winning_moves(board, opponent).is_empty();
+    Features {
+        win_now: if player_wins { 1.0 } else { 0.0 },
+        lose_now: if opp_wins { 1.0 } else { 0.0 },
+        ball_row: ball_row_feature,
+        ball_in_forbidden_col: ball_in_forbidden,
+        adj_pushers,
+        opp_adj_pushers,
+        control,
+        mobility: mob,
+        push_distance: push_dist,
+        unavoidable_win: unavoidable,
+        vulnerable,
+        ball_row_value,
+        opp_between_ball_and_goal: opp_between,
+        ball_goal_chebyshev: goal_chebyshev,
+        pusher_chain_length: pusher_chain,
+        opponent_vulnerable_pieces: opp_vulnerable,
+        positional: positional_score_with(board, player, &weights.tables),
+        ball_against_forbidden: ball_against_forbidden(board, player),
+        material_balance: material_balance_with(board, player, &weights.piece_values),
+        trapped_pieces: trapped_pieces_in(board, player, &own.moves) as f64 / max_pieces,
+        ball_cluster,
+        side_to_move: tempo(player, to_move, player_wins, opp_wins),
+        attacker_jump_options: count_jumps(&own.moves) as f64 / (max_attackers * neighbourhood),
+        goal_defense: goal_defense(board, player) as f64 / max_pieces,
+        forward_momentum: forward,
+        ball_reach: ball_reach_in(&own.moves) as f64 / neighbourhood,
+        congestion: (count_opponent_pieces_between_ball_and_goal(board, player)
+            + count_own_pieces_between_ball_and_goal(board, player)) as f64
+            / (2.0 * max_pieces),
+        pusher_proximity: pusher_proximity(board, player),
+    }
+}
 
-    let (ball_row_feature, ball_in_forbidden) = if let Some(ball_coord) = board.find_ball() {
+/// The `ball_row` and `ball_in_forbidden_col` features.
+fn ball_row_features(board: &ChessBallBoard, player: Player) -> (f64, f64) {
+    if let Some(ball_coord) = board.find_ball() {
         let dist_rows = if player == Player::White {
             (board.rows - 1) as isize - ball_coord.r as isize
         } else {
@@ -236,44 +1063,982 @@ pub fn feature_vector(board: &ChessBallBoard, player: Player) -> HashMap<String,
         (ball_row_feature, ball_in_forbidden)
     } else {
         (0.0, 0.0)
-    };
+    }
+}
 
-    let adj_pushers = count_adjacent_pushers(board, player) as f64 / 8.0;
-    let opp_adj_pushers = count_adjacent_pushers(board, opponent) as f64 / 8.0;
+/// The `adj_pushers`, `opp_adj_pushers` and `control` features.
+fn ball_neighbourhood_features(
+    board: &ChessBallBoard,
+    player: Player,
+    config: &FeatureConfig,
+) -> (f64, f64, f64) {
+    let opponent = player.opponent();
+    let adj_pushers = count_adjacent_pushers(board, player) as f64 / config.neighbourhood;
+    let opp_adj_pushers = count_adjacent_pushers(board, opponent) as f64 / config.neighbourhood;
     let (control_friendly, control_enemy) = count_control_around_ball(board, player);
-    let control = (control_friendly as f64 - control_enemy as f64) / 8.0;
+    let control = (control_friendly as f64 - control_enemy as f64) / config.neighbourhood;
+    (adj_pushers, opp_adj_pushers, control)
+}
 
-    let mob_p = mobility(board, player) as f64;
-    let mob_o = mobility(board, opponent) as f64;
-    let mob_cap = 60.0;
-    let mob = (mob_p - mob_o) / mob_cap;
+/// Feature vector similar to the Python implementation.
+/// Returns a HashMap mapping feature name to value; prefer `features` in hot paths.
+pub fn feature_vector(board: &ChessBallBoard, player: Player) -> HashMap<String, f64> {
+    features(board, player).to_map()
+}
 
-    let max_pieces = 5.0;
-    let vulnerable = vulnerable_pieces_count(board, player) as f64 / max_pieces;
+/// Feature names for exported datasets, in the column order of `feature_array`.
+///
+/// This is `Features::NAMES`: new features are only ever appended, so existing columns
+/// keep their position.
+pub fn feature_names() -> Vec<&'static str> {
+    Features::NAMES.to_vec()
+}
 
-    let push_dist = approx_push_distance(board, player);
+/// Feature values of `board` for `player`, in the order of `feature_names`.
+pub fn feature_array(board: &ChessBallBoard, player: Player) -> Vec<f64> {
+    features(board, player).as_array().to_vec()
+}
 
-    let mut unavoidable = 0.0;
-    if player_wins && !is_win_avoidable_by_opponent(board, player) {
-        unavoidable = 1.0;
+/// Weights and tables used by `evaluate_with`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeuristicWeights {
+    /// One weight per feature, in `Features::NAMES` order.
+    pub features: [f64; Features::COUNT],
+    /// Tables behind the `positional` feature.
+    pub tables: PieceSquareTables,
+    /// Piece values behind the `material_balance` feature.
+    pub piece_values: PieceValues,
+    /// Normalizers scaling raw counts into the feature ranges.
+    pub config: FeatureConfig,
+}
+
+impl HeuristicWeights {
+    /// Unit weights with the default tables and piece values, which makes `evaluate_with`
+    /// equal `evaluate`.
+    pub const DEFAULT: Self = Self {
+        features: [1.0; Features::COUNT],
+        tables: PieceSquareTables::DEFAULT,
+        piece_values: PieceValues::DEFAULT,
+        config: FeatureConfig::DEFAULT,
+    };
+}
+
+impl Default for HeuristicWeights {
+    fn default() -> Self {
+        Self::DEFAULT
     }
+}
 
-    let ball_row_value = ball_row_for_player(board, player);
-    let opp_between = count_opponent_pieces_between_ball_and_goal(board, player) as f64 / 5.0;
-
-    let mut feats = HashMap::new();
-    feats.insert("win_now".to_string(), if player_wins { 1.0 } else { 0.0 });
-    feats.insert("lose_now".to_string(), if opp_wins { 1.0 } else { 0.0 });
-    feats.insert("ball_row".to_string(), ball_row_feature);
-    feats.insert("ball_in_forbidden_col".to_string(), ball_in_forbidden);
-    feats.insert("adj_pushers".to_string(), adj_pushers);
-    feats.insert("opp_adj_pushers".to_string(), opp_adj_pushers);
-    feats.insert("control".to_string(), control);
-    feats.insert("mobility".to_string(), mob);
-    feats.insert("push_distance".to_string(), push_dist);
-    feats.insert("unavoidable_win".to_string(), unavoidable);
-    feats.insert("vulnerable".to_string(), vulnerable);
-    feats.insert("ball_row_value".to_string(), ball_row_value);
-    feats.insert("opp_between_ball_and_goal".to_string(), opp_between);
-    feats
+/// Static evaluation of `board` for `player`: the unweighted sum of its features.
+pub fn evaluate(board: &ChessBallBoard, player: Player) -> f64 {
+    features(board, player).sum()
+}
+
+/// Static evaluation of `board` for `player` as a weighted sum of its features.
+pub fn evaluate_with(board: &ChessBallBoard, player: Player, weights: &HeuristicWeights) -> f64 {
+    evaluate_for(board, player, player, weights)
+}
+
+/// `evaluate_with` for `player` in a position where `to_move` has the move; see
+/// `features_for`. Searches score nodes with this, since the side to move there need
+/// not be the player the score is for.
+pub fn evaluate_for(
+    board: &ChessBallBoard,
+    player: Player,
+    to_move: Player,
+    weights: &HeuristicWeights,
+) -> f64 {
+    weighted_sum(&features_for(board, player, to_move, weights), weights)
+}
+
+fn weighted_sum(features: &Features, weights: &HeuristicWeights) -> f64 {
+    features
+        .as_array()
+        .iter()
+        .zip(weights.features)
+        .map(|(value, weight)| value * weight)
+        .sum()
+}
+
+/// Default `k` of `evaluate_bounded`: raw scores of about ±4 map to ±0.76.
+pub const BOUNDED_SCALE: f64 = 4.0;
+
+/// `evaluate` squashed into [-1, 1] with `tanh(score / BOUNDED_SCALE)`; see
+/// `evaluate_bounded_with`.
+pub fn evaluate_bounded(board: &ChessBallBoard, player: Player) -> f64 {
+    evaluate_bounded_with(board, player, &HeuristicWeights::DEFAULT, BOUNDED_SCALE)
+}
+
+/// `evaluate_with` passed through `tanh(score / k)`, so non-terminal positions score in
+/// (-1, 1) in the same order as the raw score. A finished game scores exactly +1 for the
+/// winner, -1 for the loser and 0 for `Player::Neutral`. `k` must be positive; a larger `k` flattens less.
+pub fn evaluate_bounded_with(
+    board: &ChessBallBoard,
+    player: Player,
+    weights: &HeuristicWeights,
+    k: f64,
+) -> f64 {
+    assert!(k > 0.0, "evaluate_bounded needs a positive scale");
+    match board.winner() {
+        Some(winner) if winner == player => 1.0,
+        Some(_) if player == Player::Neutral => 0.0,
+        Some(_) => -1.0,
+        None => (evaluate_with(board, player, weights) / k).tanh(),
+    }
+}
+
+/// The `k` features contributing most to `evaluate` of `board` for `player`, by
+/// magnitude, as (name, contribution); see `top_contributors_with`.
+pub fn top_contributors(
+    board: &ChessBallBoard,
+    player: Player,
+    k: usize,
+) -> Vec<(&'static str, f64)> {
+    top_contributors_with(board, player, &HeuristicWeights::DEFAULT, k)
+}
+
+/// The `k` terms of the `evaluate_with` dot product largest in magnitude, as
+/// (name, weighted value) with the sign kept, largest first. Equal magnitudes keep
+/// `Features::NAMES` order.
+pub fn top_contributors_with(
+    board: &ChessBallBoard,
+    player: Player,
+    weights: &HeuristicWeights,
+    k: usize,
+) -> Vec<(&'static str, f64)> {
+    let values = features_with(board, player, weights).as_array();
+    let mut terms: Vec<(&'static str, f64)> = Features::NAMES
+        .iter()
+        .zip(values)
+        .zip(weights.features)
+        .map(|((&name, value), weight)| (name, value * weight))
+        .collect();
+    terms.sort_by(|a, b| b.1.abs().total_cmp(&a.1.abs()));
+    terms.truncate(k);
+    terms
+}
+
+/// `evaluate_with` of `board` for `player` once per entry of `values`, with the weight of
+/// the feature named `feature` set to that entry and every other weight at
+/// `HeuristicWeights::DEFAULT`. The features are computed once.
+///
+/// Panics if `feature` is not in `Features::NAMES`.
+pub fn weight_sensitivity(
+    board: &ChessBallBoard,
+    player: Player,
+    feature: &str,
+    values: &[f64],
+) -> Vec<f64> {
+    let index = Features::NAMES
+        .iter()
+        .position(|&name| name == feature)
+        .unwrap_or_else(|| panic!("unknown feature '{feature}'"));
+    let mut weights = HeuristicWeights::DEFAULT;
+    let features = features_with(board, player, &weights);
+    values
+        .iter()
+        .map(|&value| {
+            weights.features[index] = value;
+            weighted_sum(&features, &weights)
+        })
+        .collect()
+}
+
+/// White's advantage on `board`: `evaluate(board, White) - evaluate(board, Black)`,
+/// positive when the position favours White.
+pub fn evaluate_symmetric(board: &ChessBallBoard) -> f64 {
+    evaluate_symmetric_with(board, &HeuristicWeights::DEFAULT)
+}
+
+/// `evaluate_with` for White minus `evaluate_with` for Black. Both sides' move lists are
+/// generated once and shared by the two feature computations.
+pub fn evaluate_symmetric_with(board: &ChessBallBoard, weights: &HeuristicWeights) -> f64 {
+    let white = SideMoves::new(board, Player::White);
+    let black = SideMoves::new(board, Player::Black);
+    let score = |player, own, opp| {
+        weighted_sum(
+            &features_from(board, player, player, weights, own, opp),
+            weights,
+        )
+    };
+    score(Player::White, &white, &black) - score(Player::Black, &black, &white)
+}
+
+/// Split `evaluate` into (offense, defense): the sums of the offensive features and of
+/// the `Features::DEFENSIVE` ones. The two add up to `evaluate`.
+pub fn evaluate_components(board: &ChessBallBoard, player: Player) -> (f64, f64) {
+    evaluate_components_with(board, player, &HeuristicWeights::DEFAULT)
+}
+
+/// Split `evaluate_with` into weighted (offense, defense) sums, which add up to it.
+pub fn evaluate_components_with(
+    board: &ChessBallBoard,
+    player: Player,
+    weights: &HeuristicWeights,
+) -> (f64, f64) {
+    let mut offense = 0.0;
+    let mut defense = 0.0;
+    let values = features_with(board, player, weights).as_array();
+    for ((name, value), weight) in Features::NAMES.iter().zip(values).zip(weights.features) {
+        if Features::DEFENSIVE.contains(name) {
+            defense += value * weight;
+        } else {
+            offense += value * weight;
+        }
+    }
+    (offense, defense)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::ChessBallBoard;
+    use crate::board::{BoardBuilder, Coord, PieceType, Player, TeamSpec};
+    use crate::heuristics::{
+        BOUNDED_SCALE, FeatureConfig, Features, HeuristicWeights, PieceSquareTables, PieceValues,
+        attacker_jump_options, ball_against_forbidden, ball_cluster_size, ball_goal_chebyshev,
+        ball_reach, count_opponent_pieces_between_ball_and_goal,
+        count_own_pieces_between_ball_and_goal, evaluate, evaluate_bounded, evaluate_bounded_with,
+        evaluate_components, evaluate_components_with, evaluate_for, evaluate_symmetric,
+        evaluate_with, feature_array, feature_names, feature_vector, features, features_for,
+        features_with, forward_momentum, goal_defense, material_balance, nearest_pusher_distance,
+        opponent_vulnerable_pieces, piece_value, positional_score, positional_score_with,
+        pusher_chain_length, top_contributors, top_contributors_with, trapped_pieces,
+        weight_sensitivity,
+    };
+
+    #[test]
+    fn test_ball_goal_chebyshev_distinguishes_columns() {
+        let central = BoardBuilder::new()
+            .ball(3, 3)
+            .defender(Player::White, 2, 3)
+            .build()
+            .unwrap();
+        assert_eq!(
+            ball_goal_chebyshev(&central, Player::White),
+            1.0 - 2.0 / 5.0
+        );
+        assert_eq!(
+            ball_goal_chebyshev(&central, Player::Black),
+            1.0 - 3.0 / 5.0
+        );
+
+        // Only columns 2..=4 may be entered: from column 0 the ball is one row but two
+        // columns from White's nearest goal square, so the column distance decides.
+        let narrow = |c: usize| {
+            BoardBuilder::new()
+                .forbidden_cols(&[0, 1, 5, 6])
+                .ball(4, c)
+                .defender(Player::White, 2, 3)
+                .build()
+                .unwrap()
+        };
+        assert_eq!(
+            ball_goal_chebyshev(&narrow(0), Player::White),
+            1.0 - 2.0 / 5.0
+        );
+        assert_eq!(
+            ball_goal_chebyshev(&narrow(2), Player::White),
+            1.0 - 1.0 / 5.0
+        );
+        assert!(
+            feature_vector(&narrow(2), Player::White)["ball_goal_chebyshev"]
+                > feature_vector(&narrow(0), Player::White)["ball_goal_chebyshev"]
+        );
+    }
+
+    #[test]
+    fn test_pusher_chain_length_rewards_stacked_pushers() {
+        let single = BoardBuilder::new()
+            .ball(3, 3)
+            .attacker(Player::White, 2, 3)
+            .defender(Player::White, 1, 2)
+            .build()
+            .unwrap();
+        let stacked = BoardBuilder::new()
+            .ball(3, 3)
+            .attacker(Player::White, 2, 3)
+            .defender(Player::White, 1, 3)
+            .defender(Player::White, 0, 3)
+            .build()
+            .unwrap();
+        assert_eq!(pusher_chain_length(&single, Player::White), 1);
+        assert_eq!(pusher_chain_length(&stacked, Player::White), 3);
+        assert_eq!(pusher_chain_length(&stacked, Player::Black), 0);
+        assert!(
+            feature_vector(&stacked, Player::White)["pusher_chain_length"]
+                > feature_vector(&single, Player::White)["pusher_chain_length"]
+        );
+    }
+
+    #[test]
+    fn test_forward_momentum_is_capped_by_the_distance_to_goal() {
+        let stacked = |rows: &[usize]| {
+            let mut builder = BoardBuilder::new().ball(3, 3);
+            for &r in rows {
+                builder = builder.defender(Player::White, r, 3);
+            }
+            builder.build().unwrap()
+        };
+        // Two rows separate the ball from White's goal row, so a third pusher adds nothing.
+        let single = stacked(&[2]);
+        let column = stacked(&[2, 1, 0]);
+        assert_eq!(pusher_chain_length(&column, Player::White), 3);
+        assert_eq!(forward_momentum(&single, Player::White), 1);
+        assert_eq!(forward_momentum(&column, Player::White), 2);
+        assert_eq!(forward_momentum(&column, Player::Black), 0);
+        assert_eq!(features(&single, Player::White).forward_momentum, 0.5);
+        assert_eq!(features(&column, Player::White).forward_momentum, 1.0);
+    }
+
+    #[test]
+    fn test_evaluate_symmetric() {
+        let board = BoardBuilder::new()
+            .ball(2, 2)
+            .attacker(Player::White, 1, 2)
+            .defender(Player::White, 0, 3)
+            .defender(Player::Black, 4, 4)
+            .attacker(Player::Black, 3, 1)
+            .build()
+            .unwrap();
+        for b in [ChessBallBoard::new_game(), board] {
+            let score = evaluate_symmetric(&b);
+            let expected = evaluate(&b, Player::White) - evaluate(&b, Player::Black);
+            assert!((score - expected).abs() < 1e-9);
+            // Goals stay put under `swap_players`, so the mirror image needs the rows
+            // flipped too.
+            assert!((score + evaluate_symmetric(&b.flip_perspective())).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_ball_reach_counts_push_targets() {
+        // Pushes from (2,3) and (2,4) send the ball to (4,3) and (4,2); the push from (3,2)
+        // is blocked by Black's defender on (3,4), which is itself blocked the other way.
+        let board = BoardBuilder::new()
+            .ball(3, 3)
+            .defender(Player::White, 2, 3)
+            .attacker(Player::White, 2, 4)
+            .defender(Player::White, 3, 2)
+            .defender(Player::Black, 3, 4)
+            .build()
+            .unwrap();
+        assert_eq!(ball_reach(&board, Player::White), 2);
+        assert_eq!(features(&board, Player::White).ball_reach, 2.0 / 8.0);
+        assert_eq!(ball_reach(&board, Player::Black), 0);
+        assert_eq!(ball_reach(&ChessBallBoard::new(), Player::White), 0);
+    }
+
+    #[test]
+    fn test_features_struct_matches_map() {
+        let board = ChessBallBoard::new_game();
+        let feats = features(&board, Player::White);
+        let map = feature_vector(&board, Player::White);
+        assert_eq!(map.len(), Features::COUNT);
+        for (name, value) in feats.iter() {
+            assert_eq!(map[name], value, "feature {name}");
+        }
+        assert_eq!(map["ball_row"], feats.ball_row);
+        assert_eq!(map["mobility"], feats.mobility);
+        assert_eq!(map["pusher_chain_length"], feats.pusher_chain_length);
+        assert_eq!(
+            evaluate(&board, Player::White),
+            feats.as_array().iter().sum::<f64>()
+        );
+    }
+
+    #[test]
+    fn test_opponent_vulnerable_pieces_counts_tackle_targets() {
+        // The White defender can tackle the attacker to its right and the defender below
+        // it; the Black attacker further left has a piece behind it and is safe.
+        let board = BoardBuilder::new()
+            .ball(0, 3)
+            .defender(Player::White, 2, 3)
+            .attacker(Player::Black, 2, 4)
+            .defender(Player::Black, 3, 3)
+            .attacker(Player::Black, 2, 2)
+            .defender(Player::White, 2, 1)
+            .build()
+            .unwrap();
+        assert_eq!(opponent_vulnerable_pieces(&board, Player::White), 2);
+        assert_eq!(
+            features(&board, Player::White).opponent_vulnerable_pieces,
+            2.0 / 5.0
+        );
+    }
+
+    #[test]
+    fn test_positional_score_prefers_center() {
+        let edge = BoardBuilder::new()
+            .ball(2, 3)
+            .attacker(Player::White, 3, 1)
+            .build()
+            .unwrap();
+        let center = BoardBuilder::new()
+            .ball(2, 3)
+            .attacker(Player::White, 3, 3)
+            .build()
+            .unwrap();
+        assert!(positional_score(&center, Player::White) > positional_score(&edge, Player::White));
+        assert!(
+            features(&center, Player::White).positional > features(&edge, Player::White).positional
+        );
+
+        // Overriding the tables through the weights changes the preference.
+        let mut weights = HeuristicWeights::default();
+        assert_eq!(
+            evaluate_with(&center, Player::White, &weights),
+            evaluate(&center, Player::White)
+        );
+        weights.tables.attacker = [[0.0; 7]; 6];
+        weights.tables.attacker[3][1] = 1.0;
+        assert!(
+            positional_score_with(&edge, Player::White, &weights.tables)
+                > positional_score_with(&center, Player::White, &weights.tables)
+        );
+        assert_eq!(PieceSquareTables::default(), PieceSquareTables::DEFAULT);
+    }
+
+    #[test]
+    fn test_ball_against_forbidden_pinned_ball() {
+        let pinned = BoardBuilder::new()
+            .ball(2, 1)
+            .defender(Player::White, 1, 0)
+            .defender(Player::White, 2, 0)
+            .attacker(Player::White, 3, 0)
+            .build()
+            .unwrap();
+        assert_eq!(ball_against_forbidden(&pinned, Player::White), 1.0);
+        assert_eq!(ball_against_forbidden(&pinned, Player::Black), 0.0);
+
+        let partly = BoardBuilder::new()
+            .ball(2, 1)
+            .defender(Player::White, 2, 0)
+            .defender(Player::Black, 3, 0)
+            .build()
+            .unwrap();
+        assert_eq!(ball_against_forbidden(&partly, Player::White), 1.0 / 3.0);
+
+        let central = BoardBuilder::new()
+            .ball(2, 3)
+            .defender(Player::White, 2, 0)
+            .build()
+            .unwrap();
+        assert_eq!(ball_against_forbidden(&central, Player::White), 0.0);
+    }
+
+    #[test]
+    fn test_feature_export_order() {
+        let board = ChessBallBoard::new_game();
+        let names = feature_names();
+        let values = feature_array(&board, Player::White);
+        assert_eq!(names.len(), values.len());
+        assert_eq!(names, feature_names());
+        assert_eq!(values, feature_array(&board, Player::White));
+        let map = feature_vector(&board, Player::White);
+        for (name, value) in names.iter().zip(&values) {
+            assert_eq!(map[*name], *value, "feature {name}");
+        }
+    }
+
+    #[test]
+    fn test_incremental_update_matches_full_recompute() {
+        let custom = HeuristicWeights {
+            config: FeatureConfig {
+                max_pieces: 4.0,
+                max_attackers: 1.0,
+                neighbourhood: 3.0,
+                mobility_cap: 12.0,
+            },
+            ..HeuristicWeights::DEFAULT
+        };
+        for weights in [HeuristicWeights::DEFAULT, custom] {
+            let mut board = ChessBallBoard::new_game();
+            let mut to_move = Player::White;
+            let mut feats = features_with(&board, Player::White, &weights);
+            for ply in 0..10 {
+                let moves = crate::moves::possible_moves(&board, to_move);
+                let (mv, after) = moves[(ply * 5 + 1) % moves.len()].clone();
+                feats.update_incremental(&after, &mv, Player::White, &weights);
+                board = after;
+                to_move = to_move.opponent();
+
+                let fresh = features_with(&board, Player::White, &weights).to_map();
+                let updated = feats.to_map();
+                for name in Features::INCREMENTAL {
+                    assert_eq!(updated[name], fresh[name], "feature {name} after ply {ply}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_material_balance_values_attackers_above_defenders() {
+        let start = ChessBallBoard::new_game();
+        assert_eq!(material_balance(&start, Player::White), 0.0);
+        assert!(piece_value(PieceType::Attacker) > piece_value(PieceType::Defender));
+
+        let mut lost_attacker = start.clone();
+        lost_attacker.remove_piece(Coord { r: 4, c: 2 });
+        let mut lost_defender = start.clone();
+        lost_defender.remove_piece(Coord { r: 5, c: 1 });
+        assert!(
+            material_balance(&lost_attacker, Player::White)
+                < material_balance(&lost_defender, Player::White)
+        );
+        assert!(evaluate(&lost_attacker, Player::White) < evaluate(&lost_defender, Player::White));
+        assert!(material_balance(&lost_attacker, Player::Black) > 0.0);
+
+        // Piece values come from the weights.
+        let weights = HeuristicWeights {
+            piece_values: PieceValues {
+                attacker: 1.0,
+                defender: 3.0,
+            },
+            ..HeuristicWeights::default()
+        };
+        assert!(
+            features_with(&lost_attacker, Player::White, &weights).material_balance
+                > features_with(&lost_defender, Player::White, &weights).material_balance
+        );
+    }
+
+    #[test]
+    fn test_trapped_pieces_counts_boxed_in_piece() {
+        // The corner defender is boxed in by its own pieces and cannot tackle them.
+        let board = BoardBuilder::new()
+            .ball(3, 3)
+            .defender(Player::White, 0, 0)
+            .attacker(Player::White, 0, 1)
+            .defender(Player::White, 1, 0)
+            .defender(Player::White, 1, 1)
+            .build()
+            .unwrap();
+        assert_eq!(trapped_pieces(&board, Player::White), 1);
+        assert_eq!(
+            trapped_pieces(&ChessBallBoard::new_game(), Player::White),
+            0
+        );
+        assert_eq!(features(&board, Player::White).trapped_pieces, 1.0 / 5.0);
+    }
+
+    #[test]
+    fn test_ball_cluster_size_rewards_cohesion() {
+        let tight = BoardBuilder::new()
+            .ball(2, 3)
+            .attacker(Player::White, 3, 3)
+            .defender(Player::White, 4, 3)
+            .defender(Player::White, 4, 4)
+            .build()
+            .unwrap();
+        let scattered = BoardBuilder::new()
+            .ball(2, 3)
+            .attacker(Player::White, 3, 3)
+            .defender(Player::White, 5, 1)
+            .defender(Player::White, 5, 5)
+            .build()
+            .unwrap();
+        assert_eq!(ball_cluster_size(&tight, Player::White), 3);
+        assert_eq!(ball_cluster_size(&scattered, Player::White), 1);
+        assert_eq!(ball_cluster_size(&tight, Player::Black), 0);
+        assert_eq!(features(&tight, Player::White).ball_cluster, 1.0);
+        assert_eq!(features(&scattered, Player::White).ball_cluster, 1.0 / 3.0);
+    }
+
+    #[test]
+    fn test_side_to_move_rewards_threatening_side() {
+        // White threatens to push the ball onto its goal row.
+        let threat = BoardBuilder::new()
+            .defender(Player::White, 3, 3)
+            .ball(4, 3)
+            .defender(Player::Black, 0, 0)
+            .build()
+            .unwrap();
+        let white = features(&threat, Player::White);
+        let black = features(&threat, Player::Black);
+        assert!(white.side_to_move > black.side_to_move);
+        assert!(
+            white.side_to_move > features(&ChessBallBoard::new_game(), Player::White).side_to_move
+        );
+        assert!(evaluate(&threat, Player::White) > evaluate(&threat, Player::Black));
+    }
+
+    #[test]
+    fn test_side_to_move_follows_the_mover() {
+        let start = ChessBallBoard::new_game();
+        let weights = HeuristicWeights::DEFAULT;
+        let tempo = |player, to_move| features_for(&start, player, to_move, &weights).side_to_move;
+        assert_eq!(tempo(Player::White, Player::White), 0.1);
+        assert_eq!(tempo(Player::White, Player::Black), -0.1);
+        assert_eq!(tempo(Player::Black, Player::White), -0.1);
+        assert_eq!(
+            features_for(&start, Player::White, Player::White, &weights),
+            features_with(&start, Player::White, &weights)
+        );
+        let swing = evaluate_for(&start, Player::White, Player::White, &weights)
+            - evaluate_for(&start, Player::White, Player::Black, &weights);
+        assert!((swing - 0.2).abs() < 1e-9, "{swing}");
+
+        // A threatening mover gets the larger bonus, and the opponent loses it.
+        let threat = BoardBuilder::new()
+            .defender(Player::White, 3, 3)
+            .ball(4, 3)
+            .defender(Player::Black, 0, 0)
+            .build()
+            .unwrap();
+        assert_eq!(
+            features_for(&threat, Player::White, Player::White, &weights).side_to_move,
+            0.5
+        );
+        assert_eq!(
+            features_for(&threat, Player::Black, Player::White, &weights).side_to_move,
+            -0.5
+        );
+    }
+
+    #[test]
+    fn test_features_stay_in_range_on_larger_board() {
+        let mut board = BoardBuilder::new()
+            .size(9, 7)
+            .defender(Player::White, 8, 1)
+            .defender(Player::White, 8, 3)
+            .defender(Player::White, 8, 5)
+            .attacker(Player::White, 7, 2)
+            .attacker(Player::White, 7, 4)
+            .defender(Player::Black, 0, 1)
+            .defender(Player::Black, 0, 3)
+            .defender(Player::Black, 0, 5)
+            .attacker(Player::Black, 1, 2)
+            .attacker(Player::Black, 1, 4)
+            .ball(4, 3)
+            .build()
+            .unwrap();
+        let mut player = Player::White;
+        for _ in 0..12 {
+            for side in [Player::White, Player::Black] {
+                for (name, value) in features(&board, side).iter() {
+                    assert!((-1.0..=1.0).contains(&value), "{name} = {value}");
+                }
+            }
+            let Some((_, next)) = crate::moves::possible_moves(&board, player)
+                .into_iter()
+                .next()
+            else {
+                break;
+            };
+            board = next;
+            player = player.opponent();
+        }
+        assert_eq!(FeatureConfig::DEFAULT.mobility_cap, 40.0);
+    }
+
+    #[test]
+    fn test_feature_config_follows_the_team() {
+        let team = TeamSpec {
+            attackers: 4,
+            defenders: 3,
+        };
+        let config = FeatureConfig::for_team(&team);
+        assert_eq!(config.max_pieces, 7.0);
+        assert_eq!(config.max_attackers, 4.0);
+        assert_eq!(config.mobility_cap, 56.0);
+
+        // Four White attackers in a 2x2 block, each able to jump its three neighbours.
+        let board = BoardBuilder::new()
+            .size(9, 11)
+            .team(team)
+            .attacker(Player::White, 3, 4)
+            .attacker(Player::White, 3, 5)
+            .attacker(Player::White, 4, 4)
+            .attacker(Player::White, 4, 5)
+            .ball(7, 2)
+            .build()
+            .unwrap();
+        assert_eq!(attacker_jump_options(&board, Player::White), 12);
+        let weights = HeuristicWeights {
+            config,
+            ..HeuristicWeights::DEFAULT
+        };
+        let f = features_with(&board, Player::White, &weights);
+        assert_eq!(f.attacker_jump_options, 12.0 / 32.0);
+    }
+
+    #[test]
+    fn test_evaluate_components_sum_to_evaluate() {
+        let weights = HeuristicWeights {
+            features: std::array::from_fn(|i| 0.5 + i as f64 * 0.1),
+            ..HeuristicWeights::default()
+        };
+        for board in sample_positions() {
+            for player in [Player::White, Player::Black] {
+                let (offense, defense) = evaluate_components(&board, player);
+                assert!((offense + defense - evaluate(&board, player)).abs() < 1e-12);
+
+                let (offense, defense) = evaluate_components_with(&board, player, &weights);
+                let total = evaluate_with(&board, player, &weights);
+                assert!((offense + defense - total).abs() < 1e-12);
+            }
+        }
+        for name in Features::DEFENSIVE {
+            assert!(Features::NAMES.contains(&name), "unknown feature {name}");
+        }
+    }
+
+    #[test]
+    fn test_features_display_lists_each_feature_once() {
+        let feats = features(&ChessBallBoard::new_game(), Player::White);
+        let text = feats.to_string();
+        let names: Vec<&str> = text
+            .lines()
+            .filter_map(|line| line.split_whitespace().next())
+            .collect();
+        for name in Features::NAMES {
+            assert_eq!(names.iter().filter(|&&n| n == name).count(), 1, "{name}");
+        }
+        assert_eq!(names.len(), Features::COUNT + 1);
+        assert!(text.ends_with(&format!("{:.4}", feats.sum())));
+
+        let weights = HeuristicWeights {
+            features: [2.0; Features::COUNT],
+            ..HeuristicWeights::default()
+        };
+        assert!(
+            feats
+                .pretty(&weights)
+                .ends_with(&format!("{:.4}", 2.0 * feats.sum()))
+        );
+    }
+
+    #[test]
+    fn test_attacker_jump_options_counts_each_direction() {
+        // The attacker can leap over both neighbours but not over the ball.
+        let board = BoardBuilder::new()
+            .attacker(Player::White, 2, 3)
+            .defender(Player::Black, 3, 3)
+            .defender(Player::White, 2, 4)
+            .ball(1, 3)
+            .build()
+            .unwrap();
+        assert_eq!(attacker_jump_options(&board, Player::White), 2);
+        assert_eq!(attacker_jump_options(&board, Player::Black), 0);
+        assert_eq!(
+            features(&board, Player::White).attacker_jump_options,
+            2.0 / 16.0
+        );
+    }
+
+    #[test]
+    fn test_goal_defense_counts_pieces_guarding_own_goal() {
+        // White defends row 0, where Black wins.
+        let defended = BoardBuilder::new()
+            .defender(Player::White, 0, 2)
+            .defender(Player::White, 0, 4)
+            .attacker(Player::White, 1, 3)
+            .attacker(Player::Black, 0, 3)
+            .ball(3, 3)
+            .build()
+            .unwrap();
+        let open = BoardBuilder::new()
+            .defender(Player::White, 4, 2)
+            .defender(Player::White, 4, 4)
+            .attacker(Player::White, 3, 2)
+            .ball(3, 3)
+            .build()
+            .unwrap();
+        assert_eq!(goal_defense(&defended, Player::White), 3);
+        assert_eq!(goal_defense(&open, Player::White), 0);
+        assert_eq!(goal_defense(&defended, Player::Black), 0);
+        assert!(
+            features(&defended, Player::White).goal_defense
+                > features(&open, Player::White).goal_defense
+        );
+    }
+
+    /// Positions reached from the start by a fixed sequence of move choices.
+    fn sample_positions() -> Vec<ChessBallBoard> {
+        let mut board = ChessBallBoard::new_game();
+        let mut player = Player::White;
+        let mut positions = vec![board.clone()];
+        for ply in 0..12 {
+            let moves = crate::moves::possible_moves(&board, player);
+            if moves.is_empty()
+                || board
+                    .find_ball()
+                    .is_some_and(|b| b.r == 0 || b.r == board.rows - 1)
+            {
+                break;
+            }
+            board = moves[(ply * 7 + 3) % moves.len()].1.clone();
+            player = player.opponent();
+            positions.push(board.clone());
+        }
+        positions
+    }
+
+    #[test]
+    fn test_evaluate_is_symmetric() {
+        for board in sample_positions() {
+            let base = features(&board, Player::White);
+            let mirrored = features(&board.mirror_horizontal(), Player::White);
+            let flipped = features(&board.flip_perspective(), Player::Black);
+            for ((name, a), ((_, b), (_, c))) in
+                base.iter().zip(mirrored.iter().zip(flipped.iter()))
+            {
+                assert!((a - b).abs() < 1e-12, "{name} mirror {a} vs {b}\n{board}");
+                assert!((a - c).abs() < 1e-12, "{name} flip {a} vs {c}\n{board}");
+            }
+            let score = evaluate(&board, Player::White);
+            assert!((score - evaluate(&board.mirror_horizontal(), Player::White)).abs() < 1e-9);
+            assert!((score - evaluate(&board.flip_perspective(), Player::Black)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_top_contributors_lead_with_win_now() {
+        let board = BoardBuilder::new()
+            .defender(Player::White, 3, 3)
+            .ball(4, 3)
+            .defender(Player::Black, 0, 0)
+            .build()
+            .unwrap();
+        let top = top_contributors(&board, Player::White, 3);
+        assert_eq!(top.len(), 3);
+        assert_eq!(top[0].0, "win_now");
+        assert!(top.windows(2).all(|w| w[0].1.abs() >= w[1].1.abs()));
+
+        let mut weights = HeuristicWeights::DEFAULT;
+        weights.features = [0.0; Features::COUNT];
+        let ball_row = Features::NAMES
+            .iter()
+            .position(|&n| n == "ball_row")
+            .unwrap();
+        weights.features[ball_row] = -2.0;
+        let only = top_contributors_with(&board, Player::White, &weights, 1);
+        assert_eq!(only[0].0, "ball_row");
+        assert!(only[0].1 < 0.0);
+    }
+
+    #[test]
+    fn test_own_piece_between_ball_and_goal() {
+        // White's goal is the last row; the White defender on (4, 3) stands right in
+        // front of the ball, the Black one on (3, 0) further off to the side.
+        let board = BoardBuilder::new()
+            .ball(2, 3)
+            .attacker(Player::White, 1, 3)
+            .defender(Player::White, 4, 3)
+            .defender(Player::Black, 3, 0)
+            .build()
+            .unwrap();
+        assert_eq!(
+            count_own_pieces_between_ball_and_goal(&board, Player::White),
+            1
+        );
+        assert_eq!(
+            count_opponent_pieces_between_ball_and_goal(&board, Player::White),
+            1
+        );
+        // Black's goal is row 0: only the White attacker on row 1 is in its way.
+        assert_eq!(
+            count_own_pieces_between_ball_and_goal(&board, Player::Black),
+            0
+        );
+        assert_eq!(
+            count_opponent_pieces_between_ball_and_goal(&board, Player::Black),
+            1
+        );
+
+        let f = features(&board, Player::White);
+        assert_eq!(f.congestion, 1.0 / FeatureConfig::DEFAULT.max_pieces);
+    }
+
+    #[test]
+    fn test_evaluate_bounded_stays_in_range_and_order() {
+        let start = ChessBallBoard::new_game();
+        let mut boards: Vec<ChessBallBoard> = crate::moves::possible_moves(&start, Player::White)
+            .into_iter()
+            .map(|(_, board)| board)
+            .filter(|board| board.winner().is_none())
+            .collect();
+        boards.push(start.clone());
+        for player in [Player::White, Player::Black] {
+            let mut scored: Vec<(f64, f64)> = boards
+                .iter()
+                .map(|board| (evaluate(board, player), evaluate_bounded(board, player)))
+                .collect();
+            scored.sort_by(|a, b| a.0.total_cmp(&b.0));
+            assert!(
+                scored
+                    .iter()
+                    .all(|&(_, bounded)| bounded > -1.0 && bounded < 1.0)
+            );
+            assert!(scored.windows(2).all(|w| w[0].1 <= w[1].1));
+        }
+
+        let won = BoardBuilder::new()
+            .defender(Player::White, 4, 3)
+            .ball(5, 3)
+            .build()
+            .unwrap();
+        assert_eq!(evaluate_bounded(&won, Player::White), 1.0);
+        assert_eq!(evaluate_bounded(&won, Player::Black), -1.0);
+        assert_eq!(evaluate_bounded(&won, Player::Neutral), 0.0);
+        let wide = evaluate_bounded_with(&start, Player::White, &HeuristicWeights::DEFAULT, 100.0);
+        assert!(
+            wide.abs()
+                <= evaluate_bounded_with(
+                    &start,
+                    Player::White,
+                    &HeuristicWeights::DEFAULT,
+                    BOUNDED_SCALE
+                )
+                .abs()
+        );
+    }
+
+    #[test]
+    fn test_weight_sensitivity_follows_win_now() {
+        let board = BoardBuilder::new()
+            .defender(Player::White, 3, 3)
+            .ball(4, 3)
+            .defender(Player::Black, 0, 0)
+            .build()
+            .unwrap();
+        let values = [-2.0, 0.0, 1.0, 3.0];
+        let sweep = weight_sensitivity(&board, Player::White, "win_now", &values);
+        assert_eq!(sweep.len(), values.len());
+        assert!(sweep.windows(2).all(|w| w[0] < w[1]));
+        assert!((sweep[2] - evaluate(&board, Player::White)).abs() < 1e-9);
+
+        // Black cannot win now, so its evaluation ignores the weight.
+        let flat = weight_sensitivity(&board, Player::Black, "win_now", &values);
+        assert!(flat.windows(2).all(|w| w[0] == w[1]));
+    }
+
+    #[test]
+    fn test_nearest_pusher_distance_adjacent_and_far() {
+        let adjacent = BoardBuilder::new()
+            .attacker(Player::White, 2, 3)
+            .ball(3, 3)
+            .defender(Player::Black, 5, 6)
+            .build()
+            .unwrap();
+        let far = BoardBuilder::new()
+            .attacker(Player::White, 0, 0)
+            .ball(3, 3)
+            .defender(Player::Black, 5, 6)
+            .build()
+            .unwrap();
+        assert_eq!(nearest_pusher_distance(&adjacent, Player::White), Some(1));
+        assert_eq!(nearest_pusher_distance(&far, Player::White), Some(3));
+        assert_eq!(nearest_pusher_distance(&adjacent, Player::Black), Some(3));
+        assert_eq!(features(&adjacent, Player::White).pusher_proximity, 1.0);
+        assert!(
+            features(&far, Player::White).pusher_proximity
+                < features(&adjacent, Player::White).pusher_proximity
+        );
+
+        let mut alone = adjacent.clone();
+        alone.remove_piece(Coord { r: 5, c: 6 });
+        assert_eq!(nearest_pusher_distance(&alone, Player::Black), None);
+        assert_eq!(features(&alone, Player::Black).pusher_proximity, 0.0);
+    }
 }
@@ -0,0 +1,137 @@
+//! JSON encoding of boards, moves and search results.
+//!
+//! The crate has no serde dependency, so the few shapes needed by the language
+//! bindings are written by hand. Coordinates are encoded as `[row, col]` pairs.
+
+use crate::board::{ChessBallBoard, Coord, Player};
+use crate::minimax::choose_best_move;
+use crate::moves::{MoveInfo, MoveSpecialInfo, possible_moves};
+
+/// Encode a board as `{"rows": R, "cols": C, "cells": [["--", "WA", ...], ...],
+/// "forbidden_cols": [0, C - 1]}`.
+pub fn board_json(board: &ChessBallBoard) -> String {
+    let rows = (0..board.rows)
+        .map(|r| {
+            let cells = (0..board.cols)
+                .map(|c| match board.get_piece(Coord { r, c }) {
+                    Some(piece) => format!("\"{}\"", piece),
+                    None => "\"--\"".to_string(),
+                })
+                .collect::<Vec<_>>();
+            format!("[{}]", cells.join(","))
+        })
+        .collect::<Vec<_>>();
+    let forbidden = board
+        .forbidden_cols()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>();
+    format!(
+        "{{\"rows\":{},\"cols\":{},\"cells\":[{}],\"forbidden_cols\":[{}]}}",
+        board.rows,
+        board.cols,
+        rows.join(","),
+        forbidden.join(",")
+    )
+}
+
+/// Encode a move as `{"from": [r, c], "to": [r, c], "kind": ...}` plus the
+/// kind-specific square (`ball_to`, `jumped_over` or `pushed_to`).
+pub fn move_json(mv: &MoveInfo) -> String {
+    let extra = match &mv.special {
+        MoveSpecialInfo::SimpleMove => "\"kind\":\"simple\"".to_string(),
+        MoveSpecialInfo::BallPush { ball_to } => {
+            format!("\"kind\":\"push\",\"ball_to\":{}", coord_json(*ball_to))
+        }
+        MoveSpecialInfo::AttackerJump { jumped_over } => {
+            format!(
+                "\"kind\":\"jump\",\"jumped_over\":{}",
+                coord_json(*jumped_over)
+            )
+        }
+        MoveSpecialInfo::DefenderTackle(tackle) => format!(
+            "\"kind\":\"tackle\",\"pushed_to\":{}",
+            coord_json(tackle.pushed_piece_to)
+        ),
+    };
+    format!(
+        "{{\"from\":{},\"to\":{},{}}}",
+        coord_json(mv.from),
+        coord_json(mv.to),
+        extra
+    )
+}
+
+/// Encode every legal move of `player` as a JSON array of [`move_json`] objects.
+pub fn legal_moves_json(board: &ChessBallBoard, player: Player) -> String {
+    let moves = possible_moves(board, player)
+        .iter()
+        .map(|(mv, _)| move_json(mv))
+        .collect::<Vec<_>>();
+    format!("[{}]", moves.join(","))
+}
+
+/// Run `choose_best_move` and encode `{"move": ..., "board": ..., "score": ...}`.
+///
+/// `move` and `board` are `null` when no move was found. Infinite scores (forced
+/// wins and losses) are encoded as the strings `"Infinity"` and `"-Infinity"`.
+pub fn best_move_json(board: &ChessBallBoard, player: Player, depth: usize) -> String {
+    let (mv, board_after, score) = choose_best_move(board, player, depth);
+    let score = if score.is_finite() {
+        format!("{}", score)
+    } else if score > 0.0 {
+        "\"Infinity\"".to_string()
+    } else {
+        "\"-Infinity\"".to_string()
+    };
+    format!(
+        "{{\"move\":{},\"board\":{},\"score\":{}}}",
+        mv.as_ref().map_or("null".to_string(), move_json),
+        board_after.as_ref().map_or("null".to_string(), board_json),
+        score
+    )
+}
+
+fn coord_json(at: Coord) -> String {
+    format!("[{},{}]", at.r, at.c)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::{BoardBuilder, ChessBallBoard, Player};
+    use crate::json::{best_move_json, board_json, legal_moves_json};
+
+    #[test]
+    fn test_board_json_shape() {
+        let board = BoardBuilder::new()
+            .size(2, 3)
+            .ball(0, 1)
+            .attacker(Player::White, 1, 2)
+            .build()
+            .unwrap();
+        assert_eq!(
+            board_json(&board),
+            "{\"rows\":2,\"cols\":3,\"cells\":[[\"--\",\"NB\",\"--\"],[\"--\",\"--\",\"WA\"]],\"forbidden_cols\":[0,2]}"
+        );
+    }
+
+    #[test]
+    fn test_legal_moves_json_shape() {
+        let board = BoardBuilder::new()
+            .defender(Player::White, 2, 3)
+            .ball(2, 4)
+            .build()
+            .unwrap();
+        let json = legal_moves_json(&board, Player::White);
+        assert!(json.starts_with('[') && json.ends_with(']'));
+        assert!(json.contains("{\"from\":[2,3],\"to\":[1,3],\"kind\":\"simple\"}"));
+        assert!(json.contains("{\"from\":[2,3],\"to\":[2,4],\"kind\":\"push\",\"ball_to\":[2,5]}"));
+    }
+
+    #[test]
+    fn test_best_move_json_shape() {
+        let json = best_move_json(&ChessBallBoard::new_game(), Player::White, 1);
+        assert!(json.starts_with("{\"move\":{\"from\":"));
+        assert!(json.contains(",\"board\":{\"rows\":6,\"cols\":7,\"cells\":"));
+        assert!(json.contains(",\"score\":"));
+    }
+}
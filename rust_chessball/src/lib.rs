@@ -9,14 +9,20 @@
 //! - winning_moves: quick detection of winning moves
 //! - blocking_move: find a blocking move if available
 //! - win_avoidability: check if a win was avoidable by opponent
+//! - game: interactive game state with undo/redo history
 //! - heuristics: feature extraction & evaluation
+//! - json: hand-written JSON encoding used by the language bindings
 //! - minimax: simple minimax search
 //! - engine: canonical ChessBall rules engine for search and solving work
 //! - solver: alpha-beta search on top of the canonical engine
 //! - record: replayable ChessBall game records
+//! - rng: seeded random number generation for reproducible helpers
+//! - search_engine: `Engine` bundling weights, transposition table and limits for the legacy board
 //! - tournament: self-play tournament utilities
+//! - python: PyO3 bindings exposing a `chessball` Python module (feature `pyo3`)
 //! - partial_tablebase: conservative partial proof builder with export/visualization
 //! - weak_solve: exact reachable-graph and retrograde solver
+//! - wasm: `wasm_bindgen` wrappers for browser play (feature `wasm`)
 
 // Library root: expose modules
 pub mod agent;
@@ -25,13 +31,21 @@ pub mod arena;
 pub mod blocking_move;
 pub mod board;
 pub mod engine;
+pub mod game;
 pub mod heuristics;
+pub mod json;
 pub mod minimax;
 pub mod moves;
 pub mod partial_tablebase;
+#[cfg(feature = "pyo3")]
+pub mod python;
 pub mod record;
+pub mod rng;
+pub mod search_engine;
 pub mod solver;
 pub mod tournament;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 pub mod weak_solve;
 pub mod win_avoidability;
 pub mod winning_moves;
@@ -5,7 +5,17 @@
 //! - List legal moves for the current player with indices.
 //! - Play AI move with `ai` or by pressing Enter.
 //! - Make a human move by algebraic (e2e4) or by selecting an indexed legal move: `m 3`.
-//! - Commands: `help`, `q`, `list`, `ai`, `depth N`, `m <index>`, `h e2e4`
+//! - Take back and replay moves with `u` (undo) and `r` (redo).
+//! - Watch the AI play both sides until the game ends with `auto [plies]`.
+//! - Label a file of blank-line separated positions with the AI's best moves: `analyze <file>`.
+//! - Commands: `help`, `q`, `list`, `ai`, `auto [plies]`, `depth N`, `m <index>`, `h e2e4`, `u`, `r`,
+//!   `analyze <file>`
+//!
+//! Command-line options:
+//! - `--board <file-or-string>`: start from this position instead of the default one. The value
+//!   is read as a file if such a file exists, otherwise parsed directly; rows may be separated by
+//!   newlines or `/` (e.g. `"-- BD --/-- NB --/-- WD --"`).
+//! - `--depth <n>`: initial AI search depth (default 2).
 //!
 //! Coordinate system notes:
 //! - Board has 6 columns (a..f) and 7 rows (1..7).
@@ -13,73 +23,100 @@
 //! - This is chosen to be similar to chess algebraic where rank 1 is White's home.
 
 use chessball::board::{ChessBallBoard, Coord, Player};
-use chessball::minimax::choose_best_move;
+use chessball::game::{GameResult, GameState};
+use chessball::minimax::{analyze_batch, choose_best_move};
 use chessball::moves::{
     DefenderTackle, MoveInfo, MoveSpecialInfo, possible_moves, possible_previous_moves,
 };
-use std::io::{self, Write};
+use chessball::record::Termination;
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use std::{env, fs, process};
 
-fn coord_to_rc(token: &str, rows: usize, cols: usize) -> Option<Coord> {
-    // Expect token like "e2" where 'a'..'f' map to cols 0..cols-1 and '1'..'7' map to rows bottom->top.
-    let token = token.trim();
-    if token.len() < 2 || token.len() > 3 {
-        return None;
-    }
-    let mut chars = token.chars();
-    let file = chars.next().unwrap();
-    let file = file.to_ascii_lowercase();
-    let col = (file as u8).wrapping_sub(b'a') as isize;
-    if col < 0 || (col as usize) >= cols {
-        return None;
+const DEFAULT_DEPTH: usize = 2;
+
+const DEFAULT_AUTO_PLIES: usize = 100;
+
+/// Options given on the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CliArgs {
+    /// `--board` value: a file path or an inline board, None for the default start.
+    board: Option<String>,
+    depth: usize,
+}
+
+impl Default for CliArgs {
+    fn default() -> Self {
+        Self {
+            board: None,
+            depth: DEFAULT_DEPTH,
+        }
     }
-    let rank_str: String = chars.collect();
-    let rank: usize = match rank_str.parse() {
-        Ok(n) => n,
-        Err(_) => return None,
-    };
-    if rank < 1 || rank > rows {
-        return None;
+}
+
+/// Parse the command-line arguments (without the program name).
+fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<CliArgs, String> {
+    let mut parsed = CliArgs::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--board" => {
+                parsed.board = Some(
+                    args.next()
+                        .ok_or_else(|| "missing value for --board".to_string())?,
+                )
+            }
+            "--depth" => {
+                let raw = args
+                    .next()
+                    .ok_or_else(|| "missing value for --depth".to_string())?;
+                parsed.depth = raw
+                    .parse()
+                    .map_err(|_| format!("invalid value '{raw}' for --depth"))?;
+            }
+            other => return Err(format!("unknown argument '{other}'")),
+        }
     }
-    // Algebraic: rank 1 is bottom (white's home) -> row_index = rows - rank
-    Some(Coord {
-        r: (rows - rank),
-        c: col as usize,
-    })
+    Ok(parsed)
 }
 
-fn rc_to_coord(coord: &Coord, rows: usize) -> String {
-    // inverse of coord_to_rc
-    let file = (b'a' + (coord.c as u8)) as char;
-    // rank = rows - r
-    let rank = rows as isize - coord.r as isize;
-    format!("{}{}", file, rank)
+/// Load the `--board` value: the contents of the named file if it exists, otherwise the value
+/// itself, with `/` accepted as a row separator.
+fn load_board(spec: &str) -> Result<ChessBallBoard, String> {
+    let text = if Path::new(spec).is_file() {
+        fs::read_to_string(spec).map_err(|err| format!("failed to read {spec}: {err}"))?
+    } else {
+        spec.replace('/', "\n")
+    };
+    if text.trim().is_empty() {
+        return Err("empty board".to_string());
+    }
+    ChessBallBoard::from_repr(&text)
 }
 
 fn move_to_pretty(mi: &MoveInfo, board_rows: usize) -> String {
     let mut s = format!(
         "{}->{}",
-        rc_to_coord(&mi.from, board_rows),
-        rc_to_coord(&mi.to, board_rows)
+        mi.from.algebraic(board_rows),
+        mi.to.algebraic(board_rows)
     );
     let mut flags = Vec::new();
     match &mi.special {
         MoveSpecialInfo::BallPush { ball_to } => {
-            flags.push(format!("push ball->{}", rc_to_coord(ball_to, board_rows)))
+            flags.push(format!("push ball->{}", ball_to.algebraic(board_rows)))
         }
         MoveSpecialInfo::AttackerJump { jumped_over } => {
-            flags.push(format!(
-                "jump over {}",
-                rc_to_coord(jumped_over, board_rows)
-            ));
+            flags.push(format!("jump over {}", jumped_over.algebraic(board_rows)));
         }
         MoveSpecialInfo::DefenderTackle(DefenderTackle {
             pushed_piece_from,
             pushed_piece_to,
+            ..
         }) => {
             flags.push(format!(
                 "tackle push {}->{}",
-                rc_to_coord(pushed_piece_from, board_rows),
-                rc_to_coord(pushed_piece_to, board_rows)
+                pushed_piece_from.algebraic(board_rows),
+                pushed_piece_to.algebraic(board_rows)
             ));
         }
         MoveSpecialInfo::SimpleMove => {}
@@ -92,32 +129,29 @@ fn move_to_pretty(mi: &MoveInfo, board_rows: usize) -> String {
     s
 }
 
-fn apply_move_by_index(board: &mut ChessBallBoard, player: Player, index: usize) -> bool {
-    let moves = possible_moves(board, player);
+fn apply_move_by_index(game: &mut GameState, index: usize) -> bool {
+    let moves = possible_moves(&game.board, game.to_move);
     if index >= moves.len() {
         return false;
     }
-    let (_mv, nb) = moves.into_iter().nth(index).unwrap();
-    *board = nb;
+    let (mv, nb) = moves.into_iter().nth(index).unwrap();
+    game.play(mv, nb);
     true
 }
 
-fn try_apply_algebraic_move(
-    board: &mut ChessBallBoard,
-    player: Player,
-    src: &str,
-    dst: &str,
-) -> Result<(), String> {
+fn try_apply_algebraic_move(game: &mut GameState, src: &str, dst: &str) -> Result<(), String> {
+    let board = &game.board;
+    let player = game.to_move;
     let rows = board.rows;
     let cols = board.cols;
-    let from = coord_to_rc(src, rows, cols)
+    let from = Coord::from_algebraic(src, rows, cols)
         .ok_or_else(|| format!("Invalid source coordinate '{}'", src))?;
-    let to =
-        coord_to_rc(dst, rows, cols).ok_or_else(|| format!("Invalid dest coordinate '{}'", dst))?;
+    let to = Coord::from_algebraic(dst, rows, cols)
+        .ok_or_else(|| format!("Invalid dest coordinate '{}'", dst))?;
     // Find a legal move matching these coords
     for (mv, nb) in possible_moves(board, player) {
         if mv.from == from && mv.to == to {
-            *board = nb;
+            game.play(mv, nb);
             return Ok(());
         }
     }
@@ -190,9 +224,21 @@ fn print_help() {
     );
     println!("  h e2 e4              : also accepted");
     println!("  m <index>            : apply the legal move with given index (see 'list')");
+    println!(
+        "  auto [plies]         : AI plays both sides until a win, a draw or the ply cap (default {})",
+        DEFAULT_AUTO_PLIES
+    );
     println!("  list                 : list legal moves for current player");
     println!("  prev                 : show some possible previous moves (diagnostic)");
-    println!("  depth <n>            : set AI search depth (default 2)");
+    println!(
+        "  depth <n>            : set AI search depth (default {})",
+        DEFAULT_DEPTH
+    );
+    println!(
+        "  analyze <file>       : best move and score for the current player in each position of a file (blank-line separated)"
+    );
+    println!("  u                    : undo the last move");
+    println!("  r                    : redo the last undone move");
     println!("  q                    : quit");
     println!("  help                 : print this message");
     println!();
@@ -202,23 +248,30 @@ fn print_help() {
 }
 
 fn main() {
-    let start = "\
--- BD -- BD -- BD --\n\
--- -- BA -- BA -- --\n\
--- -- -- NB -- -- --\n\
--- -- -- -- -- -- --\n\
--- -- WA -- WA -- --\n\
--- WD -- WD -- WD --\n";
-    let mut board = ChessBallBoard::from_repr(start).expect("failed to parse start board");
-    let mut current = Player::White;
-    let mut depth = 2usize;
+    let args = parse_args(env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        eprintln!("Usage: chessball [--board <file-or-string>] [--depth <n>]");
+        process::exit(2);
+    });
+    let mut game = match &args.board {
+        Some(spec) => {
+            let board = load_board(spec).unwrap_or_else(|err| {
+                eprintln!("invalid --board: {err}");
+                process::exit(2);
+            });
+            GameState::new(board, Player::White)
+        }
+        None => GameState::new_game(),
+    };
+    let mut depth = args.depth;
+    let color = io::stdout().is_terminal();
 
     println!("Welcome to ChessBall (Rust port enhanced CLI).");
     print_help();
 
     loop {
-        println!("\nCurrent player: {:?}\n", current);
-        println!("{}", board);
+        println!("\nCurrent player: {:?}\n", game.to_move);
+        println!("{}", game.board.render(color));
         print!("cmd> ");
         io::stdout().flush().ok();
         let mut line = String::new();
@@ -229,23 +282,22 @@ fn main() {
         let line = line.trim();
         if line.is_empty() {
             // AI move
-            let (mv, nb, score) = choose_best_move(&board, current, depth);
+            let (mv, nb, score) = choose_best_move(&game.board, game.to_move, depth);
             match mv {
                 Some(m) => {
                     println!(
                         "AI chooses move: {} (score {:.2})",
-                        move_to_pretty(&m, board.rows),
+                        move_to_pretty(&m, game.board.rows),
                         score
                     );
                     if let Some(nb) = nb {
-                        board = nb;
-                        swap_player(&mut current);
+                        game.play(m, nb);
                     } else {
                         println!("(no board after move available)");
                     }
                 }
                 None => {
-                    println!("No move found for player {:?}", current);
+                    println!("No move found for player {:?}", game.to_move);
                 }
             }
         } else {
@@ -259,12 +311,20 @@ fn main() {
                     print_help();
                 }
                 "list" => {
-                    print_legal_moves(&board, current);
+                    print_legal_moves(&game.board, game.to_move);
                 }
                 "prev" => {
                     // Diagnostic: show some previous positions for opponent
-                    print_possible_prev_moves(&board, current);
+                    print_possible_prev_moves(&game.board, game.to_move);
                 }
+                "u" => match game.undo() {
+                    Some(m) => println!("Undid move {}", move_to_pretty(&m, game.board.rows)),
+                    None => println!("Nothing to undo"),
+                },
+                "r" => match game.redo() {
+                    Some(m) => println!("Redid move {}", move_to_pretty(&m, game.board.rows)),
+                    None => println!("Nothing to redo"),
+                },
                 "ai" => {
                     let n_moves = if parts.len() < 2 {
                         1
@@ -281,23 +341,49 @@ fn main() {
                         }
                     };
                     for _ in 0..n_moves {
-                        let (mv, nb, score) = choose_best_move(&board, current, depth);
+                        let (mv, nb, score) = choose_best_move(&game.board, game.to_move, depth);
                         match mv {
                             Some(m) => {
                                 println!(
                                     "AI chooses move: {} (score {:.2})",
-                                    move_to_pretty(&m, board.rows),
+                                    move_to_pretty(&m, game.board.rows),
                                     score
                                 );
                                 if let Some(nb) = nb {
-                                    board = nb;
-                                    swap_player(&mut current);
+                                    game.play(m, nb);
                                 }
                             }
                             None => println!("No AI move found"),
                         }
                     }
                 }
+                "auto" => {
+                    let max_plies = match parts.get(1).map(|n| n.parse::<usize>()) {
+                        None => DEFAULT_AUTO_PLIES,
+                        Some(Ok(n)) => n,
+                        Some(Err(_)) => {
+                            println!("Invalid ply count! Using {}", DEFAULT_AUTO_PLIES);
+                            DEFAULT_AUTO_PLIES
+                        }
+                    };
+                    let result = game.auto_play(depth, max_plies, |mv, state| {
+                        println!(
+                            "{:?} plays {}",
+                            state.to_move.opponent(),
+                            move_to_pretty(mv, state.board.rows)
+                        );
+                        println!("{}", state.board.render(color));
+                    });
+                    match result {
+                        GameResult::Win(player) => println!("{:?} wins!", player),
+                        GameResult::Draw(Termination::PlyLimit) => {
+                            println!("Stopped after {} plies", max_plies)
+                        }
+                        GameResult::Draw(termination) => {
+                            println!("Draw ({})", termination.as_str())
+                        }
+                    }
+                }
                 "depth" => {
                     if parts.len() >= 2 {
                         match parts[1].parse::<usize>() {
@@ -311,15 +397,35 @@ fn main() {
                         println!("Current depth: {}", depth);
                     }
                 }
+                "analyze" => {
+                    if parts.len() < 2 {
+                        println!("Usage: analyze <file>");
+                    } else {
+                        match fs::read_to_string(parts[1])
+                            .map_err(|err| err.to_string())
+                            .and_then(|text| ChessBallBoard::from_repr_many(&text))
+                        {
+                            Ok(boards) => {
+                                let results = analyze_batch(&boards, game.to_move, depth);
+                                for (i, (board, (mv, score))) in
+                                    boards.iter().zip(results).enumerate()
+                                {
+                                    let mv = mv.map_or("no move".to_string(), |mv| {
+                                        move_to_pretty(&mv, board.rows)
+                                    });
+                                    println!("{}: {} (score {:.2})", i + 1, mv, score);
+                                }
+                            }
+                            Err(e) => println!("Cannot analyze {}: {}", parts[1], e),
+                        }
+                    }
+                }
                 "m" => {
                     if parts.len() < 2 {
                         println!("Usage: m <index> (see 'list')");
                     } else if let Ok(idx) = parts[1].parse::<usize>() {
-                        if apply_move_by_index(&mut board, current, idx) {
-                            {
-                                println!("Applied move index {}", idx);
-                                swap_player(&mut current)
-                            };
+                        if apply_move_by_index(&mut game, idx) {
+                            println!("Applied move index {}", idx);
                         } else {
                             println!("Invalid move index {}", idx);
                         }
@@ -340,11 +446,8 @@ fn main() {
                             // First coordinate might be 2 or 3 chars; try 2 then rest
                             let a = &token[0..2];
                             let b = &token[2..];
-                            match try_apply_algebraic_move(&mut board, current, a, b) {
-                                Ok(_) => {
-                                    println!("Applied move {} -> {}", a, b);
-                                    swap_player(&mut current)
-                                }
+                            match try_apply_algebraic_move(&mut game, a, b) {
+                                Ok(_) => println!("Applied move {} -> {}", a, b),
                                 Err(e) => println!("Illegal move: {}", e),
                             }
                         } else {
@@ -356,11 +459,8 @@ fn main() {
                     } else if parts.len() == 3 {
                         let a = parts[1];
                         let b = parts[2];
-                        match try_apply_algebraic_move(&mut board, current, a, b) {
-                            Ok(_) => {
-                                println!("Applied move {} -> {}", a, b);
-                                swap_player(&mut current)
-                            }
+                        match try_apply_algebraic_move(&mut game, a, b) {
+                            Ok(_) => println!("Applied move {} -> {}", a, b),
                             Err(e) => println!("Illegal move: {}", e),
                         }
                     } else {
@@ -378,10 +478,34 @@ fn main() {
     }
 }
 
-fn swap_player(player: &mut Player) {
-    *player = match player {
-        Player::White => Player::Black,
-        Player::Black => Player::White,
-        Player::Neutral => Player::Neutral,
+#[cfg(test)]
+mod tests {
+    use super::{CliArgs, DEFAULT_DEPTH, load_board, parse_args};
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_args(args(&[])), Ok(CliArgs::default()));
+        assert_eq!(CliArgs::default().depth, DEFAULT_DEPTH);
+        assert_eq!(
+            parse_args(args(&["--depth", "4", "--board", "-- NB/-- --"])),
+            Ok(CliArgs {
+                board: Some("-- NB/-- --".to_string()),
+                depth: 4,
+            })
+        );
+        assert!(parse_args(args(&["--depth"])).is_err());
+        assert!(parse_args(args(&["--depth", "deep"])).is_err());
+        assert!(parse_args(args(&["--colour"])).is_err());
+    }
+
+    #[test]
+    fn test_load_board_inline() {
+        let board = load_board("-- BD --/-- NB --/-- WD --").unwrap();
+        assert_eq!((board.rows, board.cols), (3, 3));
+        assert!(load_board("").is_err());
     }
 }
@@ -3,130 +3,812 @@
 //! This is a straightforward translation of the Python minimax implementation.
 //! The static evaluation is a lightweight sum of heuristic features (not the full weighted linear eval).
 
+use crate::blocking_move::find_blocking_move;
 use crate::board::{ChessBallBoard, Player};
-use crate::heuristics::feature_vector;
-use crate::moves::possible_moves;
+use crate::game::{GameResult, GameState};
+use crate::heuristics::{HeuristicWeights, evaluate_for};
+use crate::moves::{MoveInfo, MoveSpecialInfo, apply_move, move_list, possible_moves};
+use crate::record::Termination;
 use crate::winning_moves::winning_moves;
 use std::f64;
+use std::fmt;
 
 /// Return the first immediate winning move (move, resulting_board) for `player` if any.
 pub fn has_immediate_win(
     board: &ChessBallBoard,
     player: Player,
-) -> Option<(crate::moves::MoveInfo, ChessBallBoard)> {
-    let wins = winning_moves(board, player);
-    if wins.is_empty() {
-        return None;
-    }
-    let mut pm = possible_moves(board, player);
-    // find first move that is in wins set by comparing from/to
-    for (mv, b2) in pm.drain(..) {
-        // find if this move results in a goal row (we can check the board)
-        if let Some(ball_coord) = b2.find_ball() {
-            let winner_row = if player == Player::Black {
-                0usize
-            } else {
-                b2.rows - 1
-            };
-            if ball_coord.r == winner_row {
-                return Some((mv, b2));
-            }
-        }
-    }
-    None
+) -> Option<(MoveInfo, ChessBallBoard)> {
+    let mv = winning_moves(board, player).into_iter().next()?;
+    let board_after = apply_move(board, &mv);
+    Some((mv, board_after))
 }
 
 /// Choose the best move for `player` using minimax to the given `depth`.
 ///
 /// Returns (best_move, best_board_after, score). Score is an f64 and uses +/-inf for terminal wins/losses.
+/// This is `search` from `player`'s point of view, so the root follows the same terminal
+/// rules as every other node. `Neutral` has no moves: the result is (None, None, 0.0).
+///
+/// Depth 0 still picks a move: it searches one ply, so each resulting board is scored
+/// once by the static evaluation (or as lost if it leaves the opponent a winning push).
 pub fn choose_best_move(
     board: &ChessBallBoard,
     player: Player,
     depth: usize,
-) -> (Option<crate::moves::MoveInfo>, Option<ChessBallBoard>, f64) {
-    let opponent = match player {
-        Player::White => Player::Black,
-        Player::Black => Player::White,
-        Player::Neutral => Player::Neutral,
-    };
-    if let Some((mv, b2)) = has_immediate_win(board, player) {
-        return (Some(mv), Some(b2), f64::INFINITY);
-    }
-    if let Some((_mv, _b2)) = has_immediate_win(board, opponent) {
-        return (None, None, f64::NEG_INFINITY);
-    }
-
-    fn minimax(
-        node_board: &ChessBallBoard,
-        to_move: Player,
-        ply: usize,
-        maximizing: bool,
-        root_player: Player,
-    ) -> (f64, Option<crate::moves::MoveInfo>, Option<ChessBallBoard>) {
-        // immediate win check
-        if let Some((mv, board_after)) = has_immediate_win(node_board, to_move) {
-            let score = if maximizing {
-                f64::INFINITY
-            } else {
-                f64::NEG_INFINITY
+) -> (Option<MoveInfo>, Option<ChessBallBoard>, f64) {
+    let (score, best_move, best_board) = search(board, player, depth.max(1), player);
+    (best_move, best_board, score)
+}
+
+/// `choose_best_move` together with the opponent's expected reply to the chosen move:
+/// (best_move, predicted_reply, score).
+///
+/// The reply is the next move of the principal variation found by the same search: the
+/// opponent's best answer to `best_move` within the remaining depth. There is none when
+/// `best_move` ends the game, when the opponent has no move, when there is no move, or at
+/// depth 1 unless the opponent can then win at once.
+pub fn choose_best_move_with_reply(
+    board: &ChessBallBoard,
+    player: Player,
+    depth: usize,
+) -> (Option<MoveInfo>, Option<MoveInfo>, f64) {
+    if player == Player::Neutral {
+        return (None, None, 0.0);
+    }
+    let (score, best_move, _, reply) =
+        minimax(board, player, depth.max(1), player, 0, &mut NoTrace);
+    (best_move, reply, score)
+}
+
+/// `choose_best_move` for `player` on each of `boards`, in order: the best move and its
+/// score, e.g. to label positions for training.
+pub fn analyze_batch(
+    boards: &[ChessBallBoard],
+    player: Player,
+    depth: usize,
+) -> Vec<(Option<MoveInfo>, f64)> {
+    boards
+        .iter()
+        .map(|board| {
+            let (best_move, _, score) = choose_best_move(board, player, depth);
+            (best_move, score)
+        })
+        .collect()
+}
+
+/// Minimax search of `board` with `to_move` to play, `depth` plies deep, scored from
+/// `root_player`'s point of view: `root_player` maximizes and the other side minimizes.
+///
+/// Returns (score, best_move, best_board_after); `choose_best_move` is this search with
+/// `to_move == root_player`. Searching for or from `Neutral` scores 0.0 with no move.
+///
+/// Terminal positions, at the root and below alike:
+/// - the ball already on a goal row: the game is over, scored as a win for that row's
+///   owner, with no move;
+/// - otherwise, `to_move` has an immediate win: it is played and scored as a win for
+///   `to_move`. This also holds when both sides threaten to win, since `to_move` gets
+///   there first.
+///
+/// A threat by the side not to move is not terminal: `to_move` may still stop it, which
+/// the search finds out. When every move loses, the first one is returned anyway.
+pub fn search(
+    board: &ChessBallBoard,
+    to_move: Player,
+    depth: usize,
+    root_player: Player,
+) -> (f64, Option<MoveInfo>, Option<ChessBallBoard>) {
+    if to_move == Player::Neutral || root_player == Player::Neutral {
+        return (0.0, None, None);
+    }
+    let (score, best_move, best_board, _) =
+        minimax(board, to_move, depth, root_player, 0, &mut NoTrace);
+    (score, best_move, best_board)
+}
+
+/// Whether `player` is in zugzwang: passing would score better for them than their best
+/// real move, so having to move at all hurts.
+///
+/// Both sides are searched `depth` plies deep (at least one): the best real move with
+/// `search`, and a pass by handing the same board to the opponent one ply shallower, so
+/// both leave the opponent to move with the same depth remaining. This is a heuristic
+/// only: passing is not a legal move, so the answer is as good as the search depth and
+/// the static evaluation. A player who can win at once, or who has no move at all, is
+/// never in zugzwang.
+pub fn is_zugzwang(board: &ChessBallBoard, player: Player, depth: usize) -> bool {
+    if player == Player::Neutral
+        || board.winner().is_some()
+        || has_immediate_win(board, player).is_some()
+    {
+        return false;
+    }
+    let depth = depth.max(1);
+    let (move_score, best_move, _) = search(board, player, depth, player);
+    if best_move.is_none() {
+        return false;
+    }
+    let (pass_score, _, _) = search(board, player.opponent(), depth - 1, player);
+    pass_score > move_score
+}
+
+/// True if `player`, to move on `board`, has already lost: the game is over with the
+/// opponent as winner, or `player` cannot win at once while the opponent threatens an
+/// immediate win that no move of `player` blocks (`find_blocking_move`).
+pub fn is_lost(board: &ChessBallBoard, player: Player) -> bool {
+    if let Some(winner) = board.winner() {
+        return winner != player;
+    }
+    has_immediate_win(board, player).is_none()
+        && !winning_moves(board, player.opponent()).is_empty()
+        && find_blocking_move(board, player).is_none()
+}
+
+/// Every legal move of `player`, in `possible_moves` order, paired with whether it is a
+/// blunder: afterwards the opponent either wins at once or has a reply after which
+/// `player` `is_lost`. Looks two plies ahead without scoring anything, so a false only
+/// rules out these short tactics.
+pub fn threat_map(board: &ChessBallBoard, player: Player) -> Vec<(MoveInfo, bool)> {
+    let opponent = player.opponent();
+    possible_moves(board, player)
+        .into_iter()
+        .map(|(mv, board_after)| {
+            let blunder = match board_after.winner() {
+                Some(winner) => winner != player,
+                None => {
+                    has_immediate_win(&board_after, opponent).is_some()
+                        || possible_moves(&board_after, opponent)
+                            .iter()
+                            .any(|(_, reply)| is_lost(reply, player))
+                }
             };
-            return (score, Some(mv), Some(board_after));
+            (mv, blunder)
+        })
+        .collect()
+}
+
+/// Whether `player`, to move on `board`, can keep the opponent from winning for the next
+/// `plies` plies against every attack. A full-width search over `possible_moves` that
+/// stops at `winner`; a side left without a move ends the line as a draw, which counts
+/// as surviving. Cost grows with the branching factor to the power of `plies`.
+pub fn can_survive(board: &ChessBallBoard, player: Player, plies: usize) -> bool {
+    fn holds(board: &ChessBallBoard, defender: Player, to_move: Player, plies: usize) -> bool {
+        if let Some(winner) = board.winner() {
+            return winner == defender;
         }
-        let other = match to_move {
-            Player::White => Player::Black,
-            Player::Black => Player::White,
-            Player::Neutral => Player::Neutral,
-        };
-        if has_immediate_win(node_board, other).is_some() {
-            let score = if maximizing {
-                f64::NEG_INFINITY
-            } else {
-                f64::INFINITY
-            };
-            return (score, None, None);
+        if plies == 0 {
+            return true;
+        }
+        let moves = possible_moves(board, to_move);
+        if moves.is_empty() {
+            return true;
+        }
+        let mut lines = moves
+            .iter()
+            .map(|(_, after)| holds(after, defender, to_move.opponent(), plies - 1));
+        if to_move == defender {
+            lines.any(|held| held)
+        } else {
+            lines.all(|held| held)
+        }
+    }
+    holds(board, player, player, plies)
+}
+
+/// Whether no line of up to `depth` plies, with either side moving first, contains a
+/// ball push that changes the ball's row, i.e. brings it closer to either goal. A
+/// finished game is not dead; a board that `is_provably_dead` is dead at any depth.
+///
+/// A bounded look-ahead, not a proof: progress may still come after `depth` plies, so
+/// draws are only reported as `DeadPosition` on `is_provably_dead`. Full-width like
+/// `can_survive`, so keep `depth` small.
+pub fn is_dead_position(board: &ChessBallBoard, depth: usize) -> bool {
+    fn progress(board: &ChessBallBoard, player: Player, depth: usize) -> bool {
+        if depth == 0 {
+            return false;
         }
-        if ply == 0 {
-            // static evaluation using heuristic features (simple linear combination not provided here)
-            // Use simple heuristic: evaluate feature sum as proxy
-            let feats = feature_vector(node_board, root_player);
-            let s: f64 = feats.values().sum();
-            return (s, None, None);
+        let Some(ball) = board.find_ball() else {
+            return false;
+        };
+        possible_moves(board, player).iter().any(|(mv, after)| {
+            matches!(mv.special, MoveSpecialInfo::BallPush { ball_to } if ball_to.r != ball.r)
+                || progress(after, player.opponent(), depth - 1)
+        })
+    }
+    if board.winner().is_some() {
+        return false;
+    }
+    board.is_provably_dead()
+        || !progress(board, Player::White, depth) && !progress(board, Player::Black, depth)
+}
+
+/// Geometric mean of the legal-move counts met while walking every line `depth` plies
+/// deep from `board`, `player` to move, like `moves::perft`. Positions without a move
+/// end their line and are left out of the mean; with none counted the result is 0.0.
+pub fn average_branching(board: &ChessBallBoard, player: Player, depth: usize) -> f64 {
+    fn walk(
+        board: &ChessBallBoard,
+        player: Player,
+        depth: usize,
+        log_sum: &mut f64,
+        nodes: &mut u64,
+    ) {
+        if depth == 0 {
+            return;
         }
-        let moves = possible_moves(node_board, to_move);
+        let moves = move_list(board, player);
         if moves.is_empty() {
-            let feats = feature_vector(node_board, root_player);
-            let s: f64 = feats.values().sum();
-            return (s, None, None);
+            return;
         }
-        if maximizing {
-            let mut best = f64::NEG_INFINITY;
-            let mut best_move = None;
-            let mut best_board = None;
-            for (mv, b_after) in moves {
-                let (score, _, _) = minimax(&b_after, other, ply - 1, false, root_player);
-                if score > best {
-                    best = score;
-                    best_move = Some(mv);
-                    best_board = Some(b_after);
-                }
-            }
-            (best, best_move, best_board)
+        *log_sum += (moves.len() as f64).ln();
+        *nodes += 1;
+        for mv in &moves {
+            walk(
+                &apply_move(board, mv),
+                player.opponent(),
+                depth - 1,
+                log_sum,
+                nodes,
+            );
+        }
+    }
+
+    let (mut log_sum, mut nodes) = (0.0, 0u64);
+    walk(board, player, depth, &mut log_sum, &mut nodes);
+    if nodes == 0 {
+        0.0
+    } else {
+        (log_sum / nodes as f64).exp()
+    }
+}
+
+/// Play `moves` from `board`, `player` first and the sides alternating, and report the
+/// final board and how the game stands.
+///
+/// Each move must be legal (one of `possible_moves`) for the side to move, and no move
+/// may follow a win. The result is `GameResult::Win` once the ball reaches a goal row; a
+/// draw by `InsufficientMaterial`, `NoLegalMoves` or `DeadPosition` when the final board
+/// ends the game as `GameState::auto_play` would; and None while the game is still open.
+pub fn play_sequence(
+    board: &ChessBallBoard,
+    player: Player,
+    moves: &[MoveInfo],
+) -> Result<(ChessBallBoard, Option<GameResult>), String> {
+    let mut state = GameState::new(board.clone(), player);
+    for (i, mv) in moves.iter().enumerate() {
+        if let Some(winner) = state.winner() {
+            return Err(format!("move {i} ({mv}) follows {winner:?}'s win"));
+        }
+        let Some((_, board_after)) = possible_moves(&state.board, state.to_move)
+            .into_iter()
+            .find(|(legal, _)| legal == mv)
+        else {
+            return Err(format!(
+                "move {i} ({mv}) is not legal for {:?}",
+                state.to_move
+            ));
+        };
+        state.play(mv.clone(), board_after);
+    }
+    let result = if let Some(winner) = state.winner() {
+        Some(GameResult::Win(winner))
+    } else if state.board.is_insufficient_material() {
+        Some(GameResult::Draw(Termination::InsufficientMaterial))
+    } else if possible_moves(&state.board, state.to_move).is_empty() {
+        Some(GameResult::Draw(Termination::NoLegalMoves))
+    } else if state.board.is_provably_dead() {
+        Some(GameResult::Draw(Termination::DeadPosition))
+    } else {
+        None
+    };
+    Ok((state.board, result))
+}
+
+/// One move tried by `search_traced`: where in the tree it was tried and the score its
+/// subtree returned, from the root player's point of view.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceEntry {
+    /// Plies between the root and the node the move was tried at.
+    pub ply: usize,
+    /// Remaining search depth at that node.
+    pub depth: usize,
+    pub to_move: Player,
+    pub mv: MoveInfo,
+    pub score: f64,
+}
+
+impl fmt::Display for TraceEntry {
+    /// `depth <d> <side> <move> = <score>`, indented two spaces per ply.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:indent$}depth {} {:?} {} = {}",
+            "",
+            self.depth,
+            self.to_move,
+            self.mv,
+            self.score,
+            indent = 2 * self.ply
+        )
+    }
+}
+
+/// `search`, also returning every move tried, in the order the search visited them
+/// (each node before its children). Format the entries with `Display`, one per line, for
+/// an indented dump of the tree.
+pub fn search_traced(
+    board: &ChessBallBoard,
+    to_move: Player,
+    depth: usize,
+    root_player: Player,
+) -> (
+    (f64, Option<MoveInfo>, Option<ChessBallBoard>),
+    Vec<TraceEntry>,
+) {
+    let mut trace = Vec::new();
+    if to_move == Player::Neutral || root_player == Player::Neutral {
+        return ((0.0, None, None), trace);
+    }
+    let (score, best_move, best_board, _) =
+        minimax(board, to_move, depth, root_player, 0, &mut trace);
+    ((score, best_move, best_board), trace)
+}
+
+/// Receives the moves tried by `minimax`. `NoTrace` compiles to nothing, so untraced
+/// searches pay no cost.
+trait Tracer {
+    /// A move is about to be searched; returns a handle for `exit`.
+    fn enter(&mut self, ply: usize, depth: usize, to_move: Player, mv: &MoveInfo) -> usize;
+    /// The move entered as `handle` scored `score`.
+    fn exit(&mut self, handle: usize, score: f64);
+}
+
+struct NoTrace;
+
+impl Tracer for NoTrace {
+    #[inline(always)]
+    fn enter(&mut self, _: usize, _: usize, _: Player, _: &MoveInfo) -> usize {
+        0
+    }
+
+    #[inline(always)]
+    fn exit(&mut self, _: usize, _: f64) {}
+}
+
+impl Tracer for Vec<TraceEntry> {
+    fn enter(&mut self, ply: usize, depth: usize, to_move: Player, mv: &MoveInfo) -> usize {
+        self.push(TraceEntry {
+            ply,
+            depth,
+            to_move,
+            mv: mv.clone(),
+            score: f64::NAN,
+        });
+        self.len() - 1
+    }
+
+    fn exit(&mut self, handle: usize, score: f64) {
+        self[handle].score = score;
+    }
+}
+
+/// Score of a won game for `root_player`.
+pub(crate) fn win_score(winner: Player, root_player: Player) -> f64 {
+    if winner == root_player {
+        f64::INFINITY
+    } else {
+        f64::NEG_INFINITY
+    }
+}
+
+/// `search`'s result plus the best move's expected reply, the next principal-variation
+/// move.
+type Line = (
+    f64,
+    Option<MoveInfo>,
+    Option<ChessBallBoard>,
+    Option<MoveInfo>,
+);
+
+/// `ply` is the remaining depth and `from_root` the distance from the root.
+fn minimax<T: Tracer>(
+    node_board: &ChessBallBoard,
+    to_move: Player,
+    ply: usize,
+    root_player: Player,
+    from_root: usize,
+    tracer: &mut T,
+) -> Line {
+    if let Some(winner) = node_board.winner() {
+        return (win_score(winner, root_player), None, None, None);
+    }
+    if let Some((mv, board_after)) = has_immediate_win(node_board, to_move) {
+        return (
+            win_score(to_move, root_player),
+            Some(mv),
+            Some(board_after),
+            None,
+        );
+    }
+    let leaf = || evaluate_for(node_board, root_player, to_move, &HeuristicWeights::DEFAULT);
+    if ply == 0 {
+        return (leaf(), None, None, None);
+    }
+    let moves = possible_moves(node_board, to_move);
+    if moves.is_empty() {
+        return (leaf(), None, None, None);
+    }
+    let maximizing = to_move == root_player;
+    let other = to_move.opponent();
+    let mut best = if maximizing {
+        f64::NEG_INFINITY
+    } else {
+        f64::INFINITY
+    };
+    let mut best_move = None;
+    let mut best_board = None;
+    let mut best_reply = None;
+    for (mv, b_after) in moves {
+        let handle = tracer.enter(from_root, ply, to_move, &mv);
+        let (score, reply, _, _) =
+            minimax(&b_after, other, ply - 1, root_player, from_root + 1, tracer);
+        tracer.exit(handle, score);
+        let better = if maximizing {
+            score > best
         } else {
-            let mut best = f64::INFINITY;
-            let mut best_move = None;
-            let mut best_board = None;
-            for (mv, b_after) in moves {
-                let (score, _, _) = minimax(&b_after, other, ply - 1, true, root_player);
-                if score < best {
-                    best = score;
-                    best_move = Some(mv);
-                    best_board = Some(b_after);
-                }
-            }
-            (best, best_move, best_board)
+            score < best
+        };
+        if better || best_move.is_none() {
+            best = score;
+            best_move = Some(mv);
+            best_board = Some(b_after);
+            best_reply = reply;
         }
     }
+    (best, best_move, best_board, best_reply)
+}
 
-    let (score, best_move, best_board) = minimax(board, player, depth, true, player);
-    (best_move, best_board, score)
+#[cfg(test)]
+mod tests {
+    use crate::board::{BoardBuilder, ChessBallBoard, Coord, Player};
+    use crate::game::GameResult;
+    use crate::minimax::{
+        analyze_batch, average_branching, can_survive, choose_best_move,
+        choose_best_move_with_reply, has_immediate_win, is_dead_position, is_lost, is_zugzwang,
+        play_sequence, search, search_traced, threat_map,
+    };
+    use crate::moves::{MoveInfo, MoveSpecialInfo, possible_moves};
+    use crate::record::Termination;
+    use crate::winning_moves::winning_moves;
+
+    #[test]
+    fn test_only_player_wins() {
+        let board = BoardBuilder::new()
+            .defender(Player::White, 3, 3)
+            .ball(4, 3)
+            .defender(Player::Black, 0, 0)
+            .build()
+            .unwrap();
+        let (mv, board_after, score) = choose_best_move(&board, Player::White, 2);
+        assert_eq!(score, f64::INFINITY);
+        assert!(winning_moves(&board, Player::White).contains(&mv.unwrap()));
+        assert_eq!(board_after.unwrap().winner(), Some(Player::White));
+    }
+
+    #[test]
+    fn test_only_opponent_threatens() {
+        // Black threatens to push the ball onto row 0, but White moves first and can stop it.
+        let board = BoardBuilder::new()
+            .defender(Player::Black, 2, 3)
+            .ball(1, 3)
+            .defender(Player::White, 0, 2)
+            .build()
+            .unwrap();
+        assert!(has_immediate_win(&board, Player::Black).is_some());
+        let (mv, board_after, score) = choose_best_move(&board, Player::White, 2);
+        assert!(mv.is_some());
+        assert!(score > f64::NEG_INFINITY);
+        assert!(has_immediate_win(&board_after.unwrap(), Player::Black).is_none());
+
+        // Scored for Black, as at an interior node, the threat is not a win either.
+        let (interior_score, interior_move, _) = search(&board, Player::White, 2, Player::Black);
+        assert!(interior_score < f64::INFINITY);
+        assert!(interior_move.is_some());
+    }
+
+    #[test]
+    fn test_both_threaten_side_to_move_wins() {
+        // On a three-row board both sides can push the ball onto their goal row.
+        let board = BoardBuilder::new()
+            .size(3, 7)
+            .defender(Player::White, 0, 3)
+            .ball(1, 3)
+            .defender(Player::Black, 2, 2)
+            .build()
+            .unwrap();
+        for player in [Player::White, Player::Black] {
+            let (mv, board_after, score) = choose_best_move(&board, player, 2);
+            assert_eq!(score, f64::INFINITY, "{player:?}");
+            assert!(matches!(
+                mv.unwrap().special,
+                MoveSpecialInfo::BallPush { .. }
+            ));
+            assert_eq!(board_after.unwrap().winner(), Some(player));
+            assert_eq!(
+                search(&board, player, 2, player.opponent()).0,
+                f64::NEG_INFINITY
+            );
+        }
+    }
+
+    #[test]
+    fn test_neutral_has_no_move_and_neutral_score() {
+        let board = ChessBallBoard::new_game();
+        assert_eq!(
+            choose_best_move(&board, Player::Neutral, 2),
+            (None, None, 0.0)
+        );
+        assert_eq!(
+            search(&board, Player::White, 2, Player::Neutral),
+            (0.0, None, None)
+        );
+    }
+
+    #[test]
+    fn test_search_matches_choose_best_move() {
+        let board = ChessBallBoard::new_game();
+        for player in [Player::White, Player::Black] {
+            let (score, mv, board_after) = search(&board, player, 1, player);
+            assert_eq!(
+                choose_best_move(&board, player, 1),
+                (mv, board_after, score)
+            );
+        }
+    }
+
+    #[test]
+    fn test_depth_zero_still_returns_a_move() {
+        let board = ChessBallBoard::new_game();
+        let (mv, board_after, score) = choose_best_move(&board, Player::White, 0);
+        let mv = mv.unwrap();
+        assert!(
+            possible_moves(&board, Player::White)
+                .iter()
+                .any(|(legal, after)| *legal == mv && Some(after) == board_after.as_ref())
+        );
+        assert_eq!(choose_best_move(&board, Player::White, 1).2, score);
+        // `search` itself keeps depth 0 as a bare static evaluation.
+        assert_eq!(search(&board, Player::White, 0, Player::White).1, None);
+    }
+
+    #[test]
+    fn test_search_traced_dumps_the_tree() {
+        let board = ChessBallBoard::new_game();
+        let (result, trace) = search_traced(&board, Player::White, 2, Player::White);
+        assert_eq!(result, search(&board, Player::White, 2, Player::White));
+        assert!(!trace.is_empty());
+        assert_eq!(trace[0].ply, 0);
+        assert_eq!(trace[0].depth, 2);
+        assert_eq!(trace[0].to_move, Player::White);
+        assert_eq!(trace[1].ply, 1);
+        assert_eq!(trace[1].to_move, Player::Black);
+        assert!(
+            trace
+                .iter()
+                .all(|entry| entry.ply <= 1 && !entry.score.is_nan())
+        );
+
+        let dump: Vec<String> = trace.iter().map(ToString::to_string).collect();
+        assert!(dump[0].starts_with("depth 2 White "));
+        assert!(dump[1].starts_with("  depth 1 Black "));
+    }
+
+    #[test]
+    fn test_zugzwang_when_every_move_unblocks_the_goal() {
+        // White's lone defender holds the ball off row 0; whatever it does, Black pushes
+        // the ball home. Passing would keep the block in place.
+        let board = BoardBuilder::new()
+            .defender(Player::White, 0, 3)
+            .ball(1, 3)
+            .defender(Player::Black, 2, 3)
+            .build()
+            .unwrap();
+        assert!(has_immediate_win(&board, Player::Black).is_none());
+        assert_eq!(
+            search(&board, Player::White, 2, Player::White).0,
+            f64::NEG_INFINITY
+        );
+        assert!(is_zugzwang(&board, Player::White, 2));
+
+        assert!(!is_zugzwang(&ChessBallBoard::new_game(), Player::White, 2));
+        assert!(!is_zugzwang(&board, Player::Neutral, 2));
+    }
+
+    #[test]
+    fn test_threat_map_flags_the_blunder() {
+        // The White defender on (0, 3) is all that stops Black pushing the ball home
+        // from (2, 3); stepping away from it hands Black the game.
+        let board = BoardBuilder::new()
+            .defender(Player::White, 0, 3)
+            .attacker(Player::White, 4, 0)
+            .ball(1, 3)
+            .defender(Player::Black, 2, 3)
+            .build()
+            .unwrap();
+        let map = threat_map(&board, Player::White);
+        assert_eq!(map.len(), possible_moves(&board, Player::White).len());
+        let guard_moves: Vec<_> = map
+            .iter()
+            .filter(|(mv, _)| mv.from == (Coord { r: 0, c: 3 }))
+            .collect();
+        assert!(!guard_moves.is_empty());
+        assert!(guard_moves.iter().all(|(_, blunder)| *blunder));
+        assert!(
+            map.iter()
+                .any(|(mv, blunder)| mv.from == (Coord { r: 4, c: 0 }) && !blunder)
+        );
+
+        let mut unguarded = board.clone();
+        unguarded.remove_piece(Coord { r: 0, c: 3 });
+        assert!(is_lost(&unguarded, Player::White));
+        assert!(!is_lost(&board, Player::White));
+    }
+
+    #[test]
+    fn test_can_survive_holds_and_collapses() {
+        // The guarded position from `test_threat_map_flags_the_blunder`: keeping the
+        // defender on (0, 3) holds, while without it Black pushes home whatever White does.
+        let board = BoardBuilder::new()
+            .defender(Player::White, 0, 3)
+            .attacker(Player::White, 4, 0)
+            .ball(1, 3)
+            .defender(Player::Black, 2, 3)
+            .build()
+            .unwrap();
+        assert!(can_survive(&board, Player::White, 2));
+        assert!(can_survive(&board, Player::White, 4));
+
+        let mut unguarded = board.clone();
+        unguarded.remove_piece(Coord { r: 0, c: 3 });
+        assert!(can_survive(&unguarded, Player::White, 1));
+        assert!(!can_survive(&unguarded, Player::White, 2));
+
+        assert!(can_survive(&ChessBallBoard::new_game(), Player::White, 2));
+    }
+
+    #[test]
+    fn test_average_branching_on_start_position() {
+        let board = ChessBallBoard::new_game();
+        let root = possible_moves(&board, Player::White).len() as f64;
+        assert!((average_branching(&board, Player::White, 1) - root).abs() < 1e-9);
+        let two_ply = average_branching(&board, Player::White, 2);
+        assert!(two_ply > 1.0 && two_ply.is_finite());
+        assert_eq!(average_branching(&board, Player::White, 0), 0.0);
+    }
+
+    #[test]
+    fn test_reply_is_the_forced_win() {
+        // Nothing White does stops the Black defender pushing the ball onto row 0.
+        let board = BoardBuilder::new()
+            .attacker(Player::White, 5, 0)
+            .ball(1, 3)
+            .defender(Player::Black, 2, 3)
+            .build()
+            .unwrap();
+        let (mv, reply, score) = choose_best_move_with_reply(&board, Player::White, 2);
+        assert!(mv.is_some());
+        assert_eq!(score, f64::NEG_INFINITY);
+        let reply = reply.unwrap();
+        assert_eq!(
+            reply.special,
+            MoveSpecialInfo::BallPush {
+                ball_to: Coord { r: 0, c: 3 }
+            }
+        );
+        assert_eq!(reply.from, Coord { r: 2, c: 3 });
+
+        // A winning move leaves nothing to reply to.
+        let (_, reply, _) = choose_best_move_with_reply(&board, Player::Black, 2);
+        assert_eq!(reply, None);
+    }
+
+    #[test]
+    fn test_play_sequence_reaches_the_goal() {
+        let board = BoardBuilder::new()
+            .defender(Player::White, 2, 3)
+            .ball(3, 3)
+            .defender(Player::Black, 0, 0)
+            .build()
+            .unwrap();
+        let moves = [
+            MoveInfo::ball_push(
+                Coord { r: 2, c: 3 },
+                Coord { r: 3, c: 3 },
+                Coord { r: 4, c: 3 },
+            ),
+            MoveInfo::simple(Coord { r: 0, c: 0 }, Coord { r: 0, c: 1 }),
+            MoveInfo::ball_push(
+                Coord { r: 3, c: 3 },
+                Coord { r: 4, c: 3 },
+                Coord { r: 5, c: 3 },
+            ),
+        ];
+        let (after, result) = play_sequence(&board, Player::White, &moves).unwrap();
+        assert_eq!(result, Some(GameResult::Win(Player::White)));
+        assert_eq!(after.winner(), Some(Player::White));
+
+        let (_, open) = play_sequence(&board, Player::White, &moves[..1]).unwrap();
+        assert_eq!(open, None);
+        // Black cannot play White's move, and nothing follows the win.
+        assert!(play_sequence(&board, Player::Black, &moves).is_err());
+        let mut too_long = moves.to_vec();
+        too_long.push(MoveInfo::simple(Coord { r: 0, c: 1 }, Coord { r: 0, c: 2 }));
+        assert!(play_sequence(&board, Player::White, &too_long).is_err());
+    }
+
+    #[test]
+    fn test_is_dead_position_on_a_blocked_ball() {
+        // Every square the ball could change rows to is taken, so nothing advances it at
+        // once; a piece stepping aside frees a push on the next ply.
+        let board = BoardBuilder::new()
+            .defender(Player::Black, 1, 2)
+            .defender(Player::Black, 1, 3)
+            .defender(Player::Black, 1, 4)
+            .ball(2, 3)
+            .defender(Player::White, 3, 2)
+            .defender(Player::White, 3, 3)
+            .defender(Player::White, 3, 4)
+            .build()
+            .unwrap();
+        assert!(is_dead_position(&board, 1));
+        assert!(!is_dead_position(&board, 2));
+        assert!(!is_dead_position(&ChessBallBoard::new_game(), 2));
+
+        // Lone pieces too far from the ball to push it within two plies: quiet for now,
+        // but nothing proves the game dead.
+        let far = BoardBuilder::new()
+            .defender(Player::White, 5, 0)
+            .ball(2, 3)
+            .defender(Player::Black, 0, 6)
+            .build()
+            .unwrap();
+        assert!(is_dead_position(&far, 2));
+        assert!(!is_dead_position(&far, 6));
+        let (_, result) = play_sequence(&far, Player::White, &[]).unwrap();
+        assert_eq!(result, None);
+
+        // With columns 2..=4 forbidden the ball on (2, 3) can never move again.
+        let frozen = BoardBuilder::new()
+            .forbidden_cols(&[2, 3, 4])
+            .defender(Player::White, 3, 3)
+            .ball(2, 3)
+            .defender(Player::Black, 1, 3)
+            .build()
+            .unwrap();
+        assert!(is_dead_position(&frozen, 0));
+        let (_, result) = play_sequence(&frozen, Player::White, &[]).unwrap();
+        assert_eq!(result, Some(GameResult::Draw(Termination::DeadPosition)));
+    }
+
+    #[test]
+    fn test_analyze_batch_scores_decisive_positions() {
+        let winning = BoardBuilder::new()
+            .defender(Player::White, 3, 3)
+            .ball(4, 3)
+            .defender(Player::Black, 0, 0)
+            .build()
+            .unwrap();
+        // Black pushes the ball home from (2, 3) whatever White does.
+        let lost = BoardBuilder::new()
+            .attacker(Player::White, 4, 0)
+            .ball(1, 3)
+            .defender(Player::Black, 2, 3)
+            .build()
+            .unwrap();
+        let results = analyze_batch(&[winning.clone(), lost], Player::White, 2);
+        assert_eq!(results.len(), 2);
+        let (best, score) = &results[0];
+        assert_eq!(*score, f64::INFINITY);
+        assert!(winning_moves(&winning, Player::White).contains(best.as_ref().unwrap()));
+        assert_eq!(results[1].1, f64::NEG_INFINITY);
+        assert!(analyze_batch(&[], Player::White, 2).is_empty());
+    }
 }
@@ -2,21 +2,116 @@
 //!
 //! Provides `possible_moves` and `possible_previous_moves` analogues of the Python code.
 //! Moves are represented by MoveInfo; generators return Vec<(MoveInfo, ChessBallBoard)> for simplicity.
+//! `possible_moves` returns a [`MoveList`], which the `smallvec` feature stores inline so
+//! typical move lists need no heap allocation of their own.
+//!
+//! `Player::Neutral` only owns the ball and never moves: every generator returns no moves
+//! for it.
 
-use crate::board::{ChessBallBoard, Coord, DIRECTIONS, Piece, PieceType};
-use crate::board::{CoordDelta, Player};
+use crate::board::{ChessBallBoard, Coord, Piece, PieceType};
+use crate::board::{CoordDelta, DIRECTIONS, Player};
 use std::clone::Clone;
+use std::collections::HashMap;
 use std::fmt;
 
+/// Storage behind `MoveList`.
+#[cfg(not(feature = "smallvec"))]
+type MoveListInner = Vec<(MoveInfo, ChessBallBoard)>;
+
+/// Storage behind `MoveList`: up to 32 moves inline; the start position has fewer than
+/// that.
+#[cfg(feature = "smallvec")]
+type MoveListInner = smallvec::SmallVec<[(MoveInfo, ChessBallBoard); 32]>;
+
+/// List of moves with the resulting boards, as returned by `possible_moves`.
+///
+/// Derefs to a slice and iterates by value. The `smallvec` feature only changes the
+/// storage, not this type, so enabling it never breaks a caller.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MoveList(MoveListInner);
+
+impl MoveList {
+    #[must_use]
+    pub fn new() -> Self {
+        Self(MoveListInner::new())
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    pub fn push(&mut self, entry: (MoveInfo, ChessBallBoard)) {
+        self.0.push(entry);
+    }
+}
+
+impl std::ops::Deref for MoveList {
+    type Target = [(MoveInfo, ChessBallBoard)];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl IntoIterator for MoveList {
+    type Item = (MoveInfo, ChessBallBoard);
+    type IntoIter = MoveListIntoIter;
+
+    fn into_iter(self) -> MoveListIntoIter {
+        MoveListIntoIter(self.0.into_iter())
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a (MoveInfo, ChessBallBoard);
+    type IntoIter = std::slice::Iter<'a, (MoveInfo, ChessBallBoard)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// By-value iterator over a `MoveList`.
+#[derive(Debug)]
+pub struct MoveListIntoIter(<MoveListInner as IntoIterator>::IntoIter);
+
+impl Iterator for MoveListIntoIter {
+    type Item = (MoveInfo, ChessBallBoard);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for MoveListIntoIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl ExactSizeIterator for MoveListIntoIter {}
+
 /// Struct describing a move
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Equality and hashing compare `from`, `to` and `special` alike: two moves between the
+/// same squares that differ in kind or in their tactical details (where the ball lands,
+/// which piece is jumped or tackled) are different moves. `push_dir` follows from the
+/// other fields, so it never tells two moves apart on its own.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct MoveInfo {
     pub from: Coord,
     pub to: Coord,
     pub special: MoveSpecialInfo,
+    /// For a ball push, the (row, col) step the ball moved by: from its old square `to`
+    /// to `ball_to`. `None` for every other move.
+    pub push_dir: Option<(isize, isize)>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum MoveSpecialInfo {
     SimpleMove,
     BallPush { ball_to: Coord },
@@ -24,12 +119,43 @@ pub enum MoveSpecialInfo {
     DefenderTackle(DefenderTackle),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Category of a move, without the details `MoveSpecialInfo` carries, for callers that
+/// only need to `match` on what kind of move it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MoveKind {
+    Simple,
+    Push,
+    Jump,
+    Tackle,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct DefenderTackle {
     pub pushed_piece_from: Coord,
     pub pushed_piece_to: Coord,
+    /// Under `MoveRules::chain_tackle`, the squares of the further pieces in the shoved
+    /// line, nearest first; each moves one square on like the first. Empty otherwise.
+    pub chain: Vec<Coord>,
 }
 
+impl DefenderTackle {
+    /// Every piece the tackle moves, as (from, to), starting with the tackled one.
+    pub fn shoved(&self) -> impl Iterator<Item = (Coord, Coord)> + '_ {
+        let dr = self.pushed_piece_to.r as isize - self.pushed_piece_from.r as isize;
+        let dc = self.pushed_piece_to.c as isize - self.pushed_piece_from.c as isize;
+        let step = move |at: Coord| Coord {
+            r: at.r.wrapping_add_signed(dr),
+            c: at.c.wrapping_add_signed(dc),
+        };
+        std::iter::once((self.pushed_piece_from, self.pushed_piece_to))
+            .chain(self.chain.iter().map(move |&at| (at, step(at))))
+    }
+}
+
+/// Bits per square index in `MoveInfo::encode`.
+const SQUARE_BITS: u32 = 12;
+const SQUARE_MASK: u32 = (1 << SQUARE_BITS) - 1;
+
 impl MoveInfo {
     /// Simple adjacent move info helper.
     pub fn simple(from: Coord, to: Coord) -> Self {
@@ -37,8 +163,158 @@ impl MoveInfo {
             from,
             to,
             special: MoveSpecialInfo::SimpleMove,
+            push_dir: None,
         }
     }
+
+    /// The null move used by search code for passing the turn: `from == to`, on the
+    /// corner square. Never generated by `possible_moves`; see `pass_move`.
+    pub fn pass() -> Self {
+        Self::simple(Coord { r: 0, c: 0 }, Coord { r: 0, c: 0 })
+    }
+
+    /// Whether this is the `pass` null move.
+    pub fn is_pass(&self) -> bool {
+        self.from == self.to
+    }
+
+    /// The category of this move.
+    pub fn kind(&self) -> MoveKind {
+        match self.special {
+            MoveSpecialInfo::SimpleMove => MoveKind::Simple,
+            MoveSpecialInfo::BallPush { .. } => MoveKind::Push,
+            MoveSpecialInfo::AttackerJump { .. } => MoveKind::Jump,
+            MoveSpecialInfo::DefenderTackle(_) => MoveKind::Tackle,
+        }
+    }
+
+    /// Whether the move only relocates the moving piece, so the position it left can come
+    /// back: simple moves and attacker jumps (the jumped piece stays put). Ball pushes
+    /// and tackles displace another piece and are irreversible, which makes them the
+    /// natural reset points for repetition and inactivity-draw counting.
+    pub fn is_reversible(&self) -> bool {
+        matches!(self.kind(), MoveKind::Simple | MoveKind::Jump)
+    }
+
+    /// Ball push helper: the piece on `from` steps onto the ball's square `to` and the
+    /// ball moves on to `ball_to`.
+    pub fn ball_push(from: Coord, to: Coord, ball_to: Coord) -> Self {
+        Self {
+            from,
+            to,
+            special: MoveSpecialInfo::BallPush { ball_to },
+            push_dir: Some((
+                ball_to.r as isize - to.r as isize,
+                ball_to.c as isize - to.c as isize,
+            )),
+        }
+    }
+
+    /// Pack the move into one integer: the row-major index of `from` in the low 12 bits,
+    /// that of `to` in the next 12 and the `MoveKind` above them. The other details
+    /// follow from these on `board`, see `decode`.
+    ///
+    /// Panics if the board has more than 4096 squares.
+    pub fn encode(&self, board: &ChessBallBoard) -> u32 {
+        assert!(
+            board.rows * board.cols <= 1 << SQUARE_BITS,
+            "board too large to encode moves"
+        );
+        let index = |at: Coord| (at.r * board.cols + at.c) as u32;
+        let kind = match self.kind() {
+            MoveKind::Simple => 0,
+            MoveKind::Push => 1,
+            MoveKind::Jump => 2,
+            MoveKind::Tackle => 3,
+        };
+        index(self.from) | index(self.to) << SQUARE_BITS | kind << (2 * SQUARE_BITS)
+    }
+
+    /// The legal move on `board` that `encode` turned into `code`, played by the owner of
+    /// the piece on its source square. None if no legal move matches.
+    pub fn decode(code: u32, board: &ChessBallBoard) -> Option<MoveInfo> {
+        let coord = |index: u32| {
+            let index = index as usize;
+            (index < board.rows * board.cols).then(|| Coord {
+                r: index / board.cols,
+                c: index % board.cols,
+            })
+        };
+        let from = coord(code & SQUARE_MASK)?;
+        let player = board.get_piece(from)?.player;
+        move_list(board, player)
+            .into_iter()
+            .find(|mv| mv.from == from && mv.encode(board) == code)
+    }
+
+    /// A sentence for logs, such as "White defender tackles the Black attacker on c4,
+    /// pushing it to c5", read off `board` as it stood before the move. Squares use the
+    /// CLI's algebraic names: files a.. left to right, rank 1 the bottom row.
+    pub fn describe(&self, board: &ChessBallBoard) -> String {
+        if self.is_pass() {
+            return "Pass".to_string();
+        }
+        let square = |at: Coord| at.algebraic(board.rows);
+        let piece_name = |at: Coord| match board.get_piece(at) {
+            Some(Piece {
+                piece_type: PieceType::Ball,
+                ..
+            }) => "the ball".to_string(),
+            Some(piece) => format!(
+                "the {:?} {}",
+                piece.player,
+                piece_type_name(piece.piece_type)
+            ),
+            None => "an empty square".to_string(),
+        };
+        let mover = match board.get_piece(self.from) {
+            Some(piece) => format!("{:?} {}", piece.player, piece_type_name(piece.piece_type)),
+            None => format!("Nothing on {}", square(self.from)),
+        };
+        match &self.special {
+            MoveSpecialInfo::SimpleMove => format!(
+                "{mover} moves from {} to {}",
+                square(self.from),
+                square(self.to)
+            ),
+            MoveSpecialInfo::BallPush { ball_to } => format!(
+                "{mover} moves from {} to {}, pushing the ball to {}",
+                square(self.from),
+                square(self.to),
+                square(*ball_to)
+            ),
+            MoveSpecialInfo::AttackerJump { jumped_over } => format!(
+                "{mover} jumps from {} over {} on {} to {}",
+                square(self.from),
+                piece_name(*jumped_over),
+                square(*jumped_over),
+                square(self.to)
+            ),
+            MoveSpecialInfo::DefenderTackle(tackle) => {
+                let mut sentence = format!(
+                    "{mover} tackles {} on {}, pushing it to {}",
+                    piece_name(tackle.pushed_piece_from),
+                    square(tackle.pushed_piece_from),
+                    square(tackle.pushed_piece_to)
+                );
+                match tackle.chain.len() {
+                    0 => {}
+                    1 => sentence.push_str(" along with the piece behind it"),
+                    n => sentence.push_str(&format!(" along with the {n} pieces behind it")),
+                }
+                sentence
+            }
+        }
+    }
+}
+
+/// Lower-case name of a piece type, for `MoveInfo::describe`.
+fn piece_type_name(piece_type: PieceType) -> &'static str {
+    match piece_type {
+        PieceType::Attacker => "attacker",
+        PieceType::Defender => "defender",
+        PieceType::Ball => "ball",
+    }
 }
 
 impl fmt::Display for MoveInfo {
@@ -54,70 +330,249 @@ impl fmt::Display for MoveInfo {
 
 /// Generate all legal moves for `player` from `board`.
 ///
-/// Returns a Vec of (MoveInfo, resulting_board).
+/// Returns a [`MoveList`] of (MoveInfo, resulting_board).
+///
+/// Pushing the ball onto the mover's own goal row is legal: the opponent wins, since
+/// the goal row reached decides the winner (see `winning_moves::own_goal_moves`).
 ///
 /// This version scans the board once and invokes lightweight per-piece-per-direction
 /// helpers to classify and generate moves. This avoids multiple full-board scans.
-pub fn possible_moves(board: &ChessBallBoard, player: Player) -> Vec<(MoveInfo, ChessBallBoard)> {
-    let mut results = Vec::new();
+pub fn possible_moves(board: &ChessBallBoard, player: Player) -> MoveList {
+    let mut results = MoveList::new();
+    possible_moves_into(board, player, &mut results);
+    results
+}
+
+/// Like [`possible_moves`], but clears `results` and fills it instead of allocating a
+/// new list, so callers can reuse one buffer across many positions.
+pub fn possible_moves_into(board: &ChessBallBoard, player: Player, results: &mut MoveList) {
+    results.clear();
+    if player == Player::Neutral {
+        return;
+    }
+    for_each_move(board, player, |mv| {
+        let board_after = apply_move(board, &mv);
+        results.push((mv, board_after));
+    });
+    // Generation must never turn a valid board into an invalid one. Test boards without
+    // a ball or with extra pieces are skipped, as is the whole check in release builds.
+    if cfg!(debug_assertions) && board.validate().is_ok() {
+        for (mv, board_after) in results.iter() {
+            debug_assert_eq!(
+                board_after.validate(),
+                Ok(()),
+                "{mv:?} leaves an invalid board"
+            );
+        }
+    }
+}
+
+/// Generate the moves of `player` without building the resulting boards.
+///
+/// Returns the same moves, in the same order, as [`possible_moves`]; use [`apply_move`]
+/// to get the board after one of them when it is actually needed.
+pub fn move_list(board: &ChessBallBoard, player: Player) -> Vec<MoveInfo> {
+    let mut moves = Vec::new();
+    if player != Player::Neutral {
+        for_each_move(board, player, |mv| moves.push(mv));
+    }
+    moves
+}
+
+/// The move generator behind `move_list` and `possible_moves`: scans the board once and
+/// hands each legal move of `player` to `f`, trying every direction of every piece with
+/// the per-piece-per-direction helpers below.
+fn for_each_move(board: &ChessBallBoard, player: Player, mut f: impl FnMut(MoveInfo)) {
     for coord in board.iter_coords() {
-        if let Some(piece) = board.get_piece(coord).cloned() {
-            if piece.player != player {
-                continue;
+        if let Some(piece) = board.get_piece(coord)
+            && piece.player == player
+        {
+            for delta in board.directions_for(piece.piece_type) {
+                let moves = [
+                    // Always attempt simple moves and ball pushes
+                    simple_move_for(board, coord, delta),
+                    ball_push_move_for(board, player, coord, piece, delta),
+                    // Specialized moves: attacker jump, defender tackle
+                    attacker_jump_move_for(board, coord, piece, delta),
+                    defender_tackle_move_for(board, player, coord, piece, delta),
+                ];
+                moves.into_iter().flatten().for_each(&mut f);
             }
-            for &delta in DIRECTIONS.iter() {
-                // Always attempt simple moves and ball pushes
-                gen_simple_move_for(board, player, coord, &piece, delta, &mut results);
-                gen_ball_push_move_for(board, player, coord, &piece, delta, &mut results);
-                // Specialized moves: attacker jump, defender tackle
-                gen_attacker_jump_move_for(board, player, coord, &piece, delta, &mut results);
-                gen_defender_tackle_move_for(board, player, coord, &piece, delta, &mut results);
+        }
+    }
+}
+
+/// `player`'s tackles against opponent pieces next to the ball, the pieces that could push
+/// it on their next move. Returns (MoveInfo, resulting_board) like `possible_moves`.
+pub fn defensive_tackles(
+    board: &ChessBallBoard,
+    player: Player,
+) -> Vec<(MoveInfo, ChessBallBoard)> {
+    let Some(ball) = board.find_ball() else {
+        return Vec::new();
+    };
+    possible_moves(board, player)
+        .into_iter()
+        .filter(|(mv, _)| match &mv.special {
+            MoveSpecialInfo::DefenderTackle(tackle) => {
+                let target = tackle.pushed_piece_from;
+                target.r.abs_diff(ball.r) <= 1 && target.c.abs_diff(ball.c) <= 1
+            }
+            _ => false,
+        })
+        .collect()
+}
+
+/// `player`'s moves grouped by the `(row, col)` of the piece that makes them, each group
+/// in `move_list` order. Squares whose piece cannot move have no entry.
+pub fn moves_by_source(
+    board: &ChessBallBoard,
+    player: Player,
+) -> HashMap<(usize, usize), Vec<MoveInfo>> {
+    let mut groups: HashMap<(usize, usize), Vec<MoveInfo>> = HashMap::new();
+    for mv in move_list(board, player) {
+        groups.entry((mv.from.r, mv.from.c)).or_default().push(mv);
+    }
+    groups
+}
+
+/// Count `player`'s moves as (attacker moves, defender moves, ball pushes).
+///
+/// Ball pushes are only counted in the last bucket, whichever piece makes them.
+pub fn mobility_by_type(board: &ChessBallBoard, player: Player) -> (usize, usize, usize) {
+    let (mut attacker, mut defender, mut pushes) = (0, 0, 0);
+    for mv in move_list(board, player) {
+        if matches!(mv.special, MoveSpecialInfo::BallPush { .. }) {
+            pushes += 1;
+        } else {
+            match board.get_piece(mv.from).map(|p| p.piece_type) {
+                Some(PieceType::Attacker) => attacker += 1,
+                Some(PieceType::Defender) => defender += 1,
+                Some(PieceType::Ball) | None => {}
             }
         }
     }
-    results
+    (attacker, defender, pushes)
+}
+
+/// The `push_dir` of every legal ball push of `player`, in `DIRECTIONS` order: the
+/// directions with a friendly piece behind the ball and an empty, non-forbidden landing
+/// square beyond it (and, under custom `MoveRules`, a piece allowed to move that way).
+pub fn legal_push_dirs(board: &ChessBallBoard, player: Player) -> Vec<(isize, isize)> {
+    let pushes: Vec<_> = move_list(board, player)
+        .into_iter()
+        .filter_map(|mv| mv.push_dir)
+        .collect();
+    DIRECTIONS
+        .iter()
+        .map(|delta| (delta.r, delta.c))
+        .filter(|dir| pushes.contains(dir))
+        .collect()
 }
 
-/// Generate a simple adjacent move for a single piece in one direction.
+/// Count the positions reached after `depth` plies starting with `player` to move (perft).
 ///
-/// This is a per-piece-per-direction helper used by `possible_moves`.
+/// Every move is followed, including moves played after the ball reached a goal row, so
+/// the count measures move generation rather than game-tree size.
+pub fn perft(board: &ChessBallBoard, player: Player, depth: usize) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    let moves = move_list(board, player);
+    if depth == 1 {
+        return moves.len() as u64;
+    }
+    let opponent = player.opponent();
+    moves
+        .iter()
+        .map(|mv| perft(&apply_move(board, mv), opponent, depth - 1))
+        .sum()
+}
+
+/// Pass the turn, for null-move pruning and zugzwang studies only: passing is not a
+/// legal move. Returns the `MoveInfo::pass` move, the board after it and the side to move
+/// next. The board is unchanged except that the tackle memory, which only bound
+/// `player`'s reply, is cleared.
+pub fn pass_move(board: &ChessBallBoard, player: Player) -> (MoveInfo, ChessBallBoard, Player) {
+    let mv = MoveInfo::pass();
+    let board_after = apply_move(board, &mv);
+    (mv, board_after, player.opponent())
+}
+
+/// Return the board after playing `mv`, a legal move on `board`, or passing for
+/// `MoveInfo::pass`.
 ///
-/// - board: current board
-/// - player: moving player
-/// - r,c: coordinates of the moving piece
-/// - piece: the piece at (r,c) (borrowed)
-/// - dr,dc: direction to attempt
-/// - results: append generated moves here
-fn gen_simple_move_for(
-    board: &ChessBallBoard,
-    _player: Player,
-    from: Coord,
-    piece: &Piece,
-    delta: CoordDelta,
-    results: &mut Vec<(MoveInfo, ChessBallBoard)>,
-) {
-    if let Some(to) = from + delta
-        && board.get_piece(to).is_none()
-    {
-        let mut newb = board.clone();
-        newb.prev_tackle = None;
-        newb.remove_piece(from);
-        newb.place_piece(to, piece.clone());
-        results.push((MoveInfo::simple(from, to), newb));
+/// Panics if `mv.from` is empty.
+pub fn apply_move(board: &ChessBallBoard, mv: &MoveInfo) -> ChessBallBoard {
+    if mv.is_pass() {
+        let mut passed = board.clone();
+        passed.prev_tackle = None;
+        return passed;
     }
+    let piece = board
+        .get_piece(mv.from)
+        .cloned()
+        .expect("no piece on the move's source square");
+    let mut newb = board.clone();
+    newb.prev_tackle = None;
+    match &mv.special {
+        MoveSpecialInfo::SimpleMove | MoveSpecialInfo::AttackerJump { .. } => {
+            newb.remove_piece(mv.from);
+            newb.place_piece(mv.to, piece);
+        }
+        MoveSpecialInfo::BallPush { ball_to } => {
+            newb.place_ball(*ball_to);
+            newb.place_piece(mv.to, piece);
+            newb.remove_piece(mv.from);
+        }
+        MoveSpecialInfo::DefenderTackle(tackle) => {
+            // push opponent to beyond, the far end of a chain first
+            let shoved: Vec<(Coord, Coord)> = tackle.shoved().collect();
+            for &(pushed_from, pushed_to) in shoved.iter().rev() {
+                let pushed_piece = board
+                    .get_piece(pushed_from)
+                    .cloned()
+                    .expect("no piece to tackle");
+                newb.remove_piece(pushed_from);
+                newb.place_piece(pushed_to, pushed_piece);
+            }
+            // move own piece to freed position
+            newb.remove_piece(mv.from);
+            newb.place_piece(mv.to, piece);
+            newb.prev_tackle = Some(tackle.clone());
+        }
+    }
+    // Boards without a ball occur in tests; otherwise this means exactly one ball remains.
+    debug_assert_eq!(
+        newb.ball_count(),
+        board.ball_count(),
+        "move {mv} changed the number of balls"
+    );
+    newb
 }
 
-/// Generate a ball-push move for a single piece in one direction.
+/// The simple adjacent move of the piece on `from` in one direction, if that square is
+/// empty.
 ///
-/// This is a per-piece-per-direction helper used by `possible_moves`.
-fn gen_ball_push_move_for(
+/// This is a per-piece-per-direction helper used by `for_each_move`.
+fn simple_move_for(board: &ChessBallBoard, from: Coord, delta: CoordDelta) -> Option<MoveInfo> {
+    let to = (from + delta)?;
+    board
+        .get_piece(to)
+        .is_none()
+        .then(|| MoveInfo::simple(from, to))
+}
+
+/// The ball-push move of `piece` on `from` in one direction, if it is legal.
+///
+/// This is a per-piece-per-direction helper used by `for_each_move`.
+fn ball_push_move_for(
     board: &ChessBallBoard,
     player: Player,
     from: Coord,
     piece: &Piece,
     delta: CoordDelta,
-    results: &mut Vec<(MoveInfo, ChessBallBoard)>,
-) {
+) -> Option<MoveInfo> {
     assert!(piece.player == player);
     if let Some(ball_coord) = from + delta
         && let Some(ball_dest) = ball_coord + delta
@@ -127,128 +582,123 @@ fn gen_ball_push_move_for(
                 player: Player::Neutral,
             })
         && board.get_piece(ball_dest).is_none()
+        && !board.is_forbidden_col(ball_dest)
     {
-        let mut new_board = board.clone();
-        new_board.prev_tackle = None;
-        new_board.place_ball(ball_dest);
-        new_board.place_piece(ball_coord, piece.clone());
-        new_board.remove_piece(from);
-        let info = MoveInfo {
-            from,
-            to: ball_coord,
-            special: MoveSpecialInfo::BallPush { ball_to: ball_dest },
-        };
-        results.push((info, new_board));
+        return Some(MoveInfo::ball_push(from, ball_coord, ball_dest));
     }
+    None
 }
 
-/// Generate an attacker jump move for a single piece in one direction.
+/// The attacker jump move of `piece` on `from` in one direction, if it is legal.
 ///
-/// This is a per-piece-per-direction helper used by `possible_moves`.
-fn gen_attacker_jump_move_for(
+/// This is a per-piece-per-direction helper used by `for_each_move`.
+fn attacker_jump_move_for(
     board: &ChessBallBoard,
-    _player: Player,
     from: Coord,
     piece: &Piece,
     delta: CoordDelta,
-    results: &mut Vec<(MoveInfo, ChessBallBoard)>,
-) {
+) -> Option<MoveInfo> {
     // Only attackers can jump
     if piece.piece_type != PieceType::Attacker {
-        return;
+        return None;
     }
-    if let Some(jumped_over_coord) = from + delta
-        && let Some(destination) = jumped_over_coord + delta
+    let jumped_over_coord = (from + delta)?;
+    let destination = (jumped_over_coord + delta)?;
+    if let Some(prev_tackle) = &board.prev_tackle
+        && (prev_tackle.pushed_piece_from, prev_tackle.pushed_piece_to) == (jumped_over_coord, from)
     {
-        if let Some(prev_tackle) = &board.prev_tackle
-            && (prev_tackle.pushed_piece_from, prev_tackle.pushed_piece_to)
-                == (jumped_over_coord, from)
-        {
-            // Not allowed to jump over defender who tackled us in previous turn
-            return;
-        }
-        if board.is_on_board(destination)
-            && let Some(jumped_piece) = board.get_piece(jumped_over_coord)
-            && board.get_piece(destination).is_none()
-            && jumped_piece.piece_type != PieceType::Ball
-        {
-            let mut newb = board.clone();
-            newb.prev_tackle = None;
-            newb.remove_piece(from);
-            newb.place_piece(destination, piece.clone());
-            let info = MoveInfo {
-                from,
-                to: destination,
-                special: MoveSpecialInfo::AttackerJump {
-                    jumped_over: jumped_over_coord,
-                },
-            };
-            results.push((info, newb));
-        }
+        // Not allowed to jump over defender who tackled us in previous turn
+        return None;
+    }
+    if board.is_on_board(destination)
+        && let Some(jumped_piece) = board.get_piece(jumped_over_coord)
+        && board.get_piece(destination).is_none()
+        && jumped_piece.piece_type != PieceType::Ball
+    {
+        return Some(MoveInfo {
+            from,
+            to: destination,
+            special: MoveSpecialInfo::AttackerJump {
+                jumped_over: jumped_over_coord,
+            },
+            push_dir: None,
+        });
     }
+    None
 }
 
-/// Generate a defender tackle move for a single piece in one direction.
+/// The defender tackle move of `piece` on `from` in one direction, if it is legal.
 ///
-/// This is a per-piece-per-direction helper used by `possible_moves`.
-fn gen_defender_tackle_move_for(
+/// This is a per-piece-per-direction helper used by `for_each_move`.
+fn defender_tackle_move_for(
     board: &ChessBallBoard,
     player: Player,
     from: Coord,
     piece: &Piece,
     delta: CoordDelta,
-    results: &mut Vec<(MoveInfo, ChessBallBoard)>,
-) {
+) -> Option<MoveInfo> {
     // Only defenders can tackle
     if piece.piece_type != PieceType::Defender {
-        return;
+        return None;
     }
-    if let Some(to) = from + delta
-        && let Some(pushed_to) = to + delta
+    let to = (from + delta)?;
+    let pushed_to = (to + delta)?;
+    if let Some(prev_tackle) = &board.prev_tackle
+        && (prev_tackle.pushed_piece_from, prev_tackle.pushed_piece_to) == (to, from)
     {
-        if let Some(prev_tackle) = &board.prev_tackle
-            && (prev_tackle.pushed_piece_from, prev_tackle.pushed_piece_to) == (to, from)
-        {
-            // Not allowed to tackle defender who tackled us in previous turn
-            return;
-        }
-        if board.is_on_board(pushed_to)
-            && let Some(pushed_piece) = board.get_piece(to)
-            && board.get_piece(pushed_to).is_none()
-            && pushed_piece.player != player
-            && pushed_piece.piece_type != PieceType::Ball
-        {
-            let mut newb = board.clone();
-            // push opponent to beyond
-            newb.remove_piece(to);
-            newb.place_piece(pushed_to, pushed_piece.clone());
-            // move own piece to freed position
-            newb.remove_piece(from);
-            newb.place_piece(to, piece.clone());
-            let tackle = DefenderTackle {
-                pushed_piece_from: to,
-                pushed_piece_to: pushed_to,
-            };
-            let info = MoveInfo {
-                from,
-                to,
-                special: MoveSpecialInfo::DefenderTackle(tackle.clone()),
-            };
-            newb.prev_tackle = Some(tackle);
-            results.push((info, newb));
-        }
+        // Not allowed to tackle defender who tackled us in previous turn
+        return None;
+    }
+    let tackleable = |at: Coord| {
+        board
+            .get_piece(at)
+            .is_some_and(|p| p.player != player && p.piece_type != PieceType::Ball)
+    };
+    if !board.is_on_board(pushed_to) || !tackleable(to) {
+        return None;
+    }
+    // Under chain tackles, every further opponent piece in line is shoved along too.
+    let mut chain = Vec::new();
+    let mut beyond = pushed_to;
+    while board.move_rules().chain_tackle() && tackleable(beyond) {
+        chain.push(beyond);
+        beyond = (beyond + delta)?;
+    }
+    if board.get_piece(beyond).is_some() {
+        return None;
     }
+    Some(MoveInfo {
+        from,
+        to,
+        special: MoveSpecialInfo::DefenderTackle(DefenderTackle {
+            pushed_piece_from: to,
+            pushed_piece_to: pushed_to,
+            chain,
+        }),
+        push_dir: None,
+    })
 }
 
 /// Generate candidate previous moves (retrograde) for `player` that could have led to `board`
+///
+/// Each entry is a move of `player` and the board it was played from, such that playing
+/// the move there gives `board` back (up to the tackle memory, which is unknown and left
+/// empty in the returned boards). Simple moves, ball pushes and attacker jumps are
+/// reconstructed when `board.prev_tackle` is empty; when it is set, the last move was
+/// that tackle and it is the only candidate.
 pub fn possible_previous_moves(
     board: &ChessBallBoard,
     player: Player,
 ) -> Vec<(MoveInfo, ChessBallBoard)> {
-    // Conservative retrograde support: reconstruct simple one-step predecessor
-    // positions only. This keeps the helper useful for diagnostics without
-    // inventing speculative reverse push/jump/tackle states.
     let mut prevs = Vec::new();
+    if player == Player::Neutral {
+        return prevs;
+    }
+
+    if let Some(tackle) = &board.prev_tackle {
+        prevs.extend(previous_tackle(board, player, tackle));
+        return prevs;
+    }
 
     for to in board.iter_coords() {
         let Some(piece) = board.get_piece(to).cloned() else {
@@ -258,53 +708,438 @@ pub fn possible_previous_moves(
             continue;
         }
 
-        for &delta in DIRECTIONS.iter() {
+        for delta in board.directions_for(piece.piece_type) {
             let Some(from) = to - delta else {
                 continue;
             };
-            if board.get_piece(from).is_some() {
-                continue;
+
+            if board.get_piece(from).is_none() {
+                // Simple move from `from` to `to`.
+                let mut prev_board = board.clone();
+                prev_board.prev_tackle = None;
+                prev_board.remove_piece(to);
+                prev_board.place_piece(from, piece.clone());
+                prevs.push((MoveInfo::simple(from, to), prev_board));
+
+                // Ball push: the ball was on `to` and went on to `ball_to`.
+                if let Some(ball_to) = to + delta
+                    && board.find_ball() == Some(ball_to)
+                    && !board.is_forbidden_col(ball_to)
+                {
+                    let mut prev_board = board.clone();
+                    prev_board.prev_tackle = None;
+                    prev_board.remove_piece(ball_to);
+                    prev_board.place_ball(to);
+                    prev_board.place_piece(from, piece.clone());
+                    let info = MoveInfo::ball_push(from, to, ball_to);
+                    prevs.push((info, prev_board));
+                }
             }
 
-            let mut prev_board = board.clone();
-            prev_board.prev_tackle = None;
-            prev_board.remove_piece(to);
-            prev_board.place_piece(from, piece.clone());
-            prevs.push((MoveInfo::simple(from, to), prev_board));
+            // Attacker jump from two squares back over the piece on `from`.
+            if piece.piece_type == PieceType::Attacker
+                && let Some(jumped) = board.get_piece(from)
+                && jumped.piece_type != PieceType::Ball
+                && let Some(jump_from) = from - delta
+                && board.get_piece(jump_from).is_none()
+            {
+                let mut prev_board = board.clone();
+                prev_board.prev_tackle = None;
+                prev_board.remove_piece(to);
+                prev_board.place_piece(jump_from, piece.clone());
+                let info = MoveInfo {
+                    from: jump_from,
+                    to,
+                    special: MoveSpecialInfo::AttackerJump { jumped_over: from },
+                    push_dir: None,
+                };
+                prevs.push((info, prev_board));
+            }
         }
     }
 
     prevs
 }
 
+/// Undo the tackle recorded in `board.prev_tackle`, if `player` could have played it.
+fn previous_tackle(
+    board: &ChessBallBoard,
+    player: Player,
+    tackle: &DefenderTackle,
+) -> Option<(MoveInfo, ChessBallBoard)> {
+    let to = tackle.pushed_piece_from;
+    let delta = CoordDelta {
+        r: tackle.pushed_piece_to.r as isize - to.r as isize,
+        c: tackle.pushed_piece_to.c as isize - to.c as isize,
+        rows: board.rows,
+        cols: board.cols,
+    };
+    let from = (to - delta)?;
+    let defender = board.get_piece(to)?.clone();
+    let shoved = tackle
+        .shoved()
+        .map(|(pushed_from, pushed_to)| Some((pushed_from, board.get_piece(pushed_to)?.clone())))
+        .collect::<Option<Vec<_>>>()?;
+    if defender.player != player
+        || defender.piece_type != PieceType::Defender
+        || shoved
+            .iter()
+            .any(|(_, pushed)| pushed.player == player || pushed.piece_type == PieceType::Ball)
+        || board.get_piece(from).is_some()
+    {
+        return None;
+    }
+    let mut prev_board = board.clone();
+    prev_board.prev_tackle = None;
+    for (_, pushed_to) in tackle.shoved() {
+        prev_board.remove_piece(pushed_to);
+    }
+    prev_board.place_piece(from, defender);
+    for (pushed_from, pushed) in shoved {
+        prev_board.place_piece(pushed_from, pushed);
+    }
+    let info = MoveInfo {
+        from,
+        to,
+        special: MoveSpecialInfo::DefenderTackle(tackle.clone()),
+        push_dir: None,
+    };
+    Some((info, prev_board))
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
+    use proptest::prelude::*;
+
     use crate::{
-        board::{ChessBallBoard, Coord, CoordDelta, DIRECTIONS, Piece, PieceType, Player},
-        moves::{DefenderTackle, MoveSpecialInfo, possible_moves, possible_previous_moves},
+        board::{
+            BoardBuilder, ChessBallBoard, Coord, CoordDelta, DIRECTIONS, MoveRules, Piece,
+            PieceType, Player,
+        },
+        moves::{
+            DefenderTackle, MoveInfo, MoveKind, MoveList, MoveSpecialInfo, apply_move,
+            defensive_tackles, legal_push_dirs, mobility_by_type, move_list, moves_by_source,
+            pass_move, perft, possible_moves, possible_moves_into, possible_previous_moves,
+        },
+        winning_moves::winning_moves,
     };
 
     // Tests for the top-level behavior (unchanged semantics)
 
     #[test]
     fn test_possible_moves_push_move() {
-        let mut b = ChessBallBoard::new();
-        b.place_piece(
-            Coord { r: 2, c: 3 },
-            Piece {
-                piece_type: PieceType::Defender,
-                player: Player::White,
-            },
+        let b = BoardBuilder::new()
+            .defender(Player::White, 2, 3)
+            .ball(2, 4)
+            .build()
+            .unwrap();
+        let found_push = possible_moves(&b, Player::White)
+            .iter()
+            .any(|(info, _newboard)| matches!(info.special, MoveSpecialInfo::BallPush { .. }));
+        assert!(found_push);
+    }
+
+    #[test]
+    fn test_push_keeps_a_single_ball() {
+        let b = BoardBuilder::new()
+            .defender(Player::White, 2, 3)
+            .ball(2, 4)
+            .build()
+            .unwrap();
+        let (push, after) = possible_moves(&b, Player::White)
+            .into_iter()
+            .find(|(info, _)| matches!(info.special, MoveSpecialInfo::BallPush { .. }))
+            .unwrap();
+        assert_eq!(after.ball_count(), 1);
+        assert_eq!(apply_move(&b, &push).ball_count(), 1);
+        assert_eq!(after.find_ball(), Some(Coord { r: 2, c: 5 }));
+    }
+
+    #[test]
+    fn test_push_dir_follows_the_ball() {
+        let b = BoardBuilder::new()
+            .defender(Player::White, 1, 2)
+            .ball(2, 3)
+            .build()
+            .unwrap();
+        let ball_from = b.find_ball().unwrap();
+        let (push, after) = possible_moves(&b, Player::White)
+            .into_iter()
+            .find(|(info, _)| matches!(info.special, MoveSpecialInfo::BallPush { .. }))
+            .unwrap();
+        let ball_to = after.find_ball().unwrap();
+        let step = (
+            ball_to.r as isize - ball_from.r as isize,
+            ball_to.c as isize - ball_from.c as isize,
         );
-        b.place_ball(Coord { r: 2, c: 4 });
-        let found_push =
+        assert_eq!(step, (1, 1));
+        assert_eq!(push.push_dir, Some(step));
+
+        // The reverse generator describes the same push the same way.
+        let undone = possible_previous_moves(&after, Player::White);
+        assert!(
+            undone
+                .iter()
+                .any(|(info, before)| *info == push && *before == b)
+        );
+        assert!(
             possible_moves(&b, Player::White)
                 .iter()
-                .any(|(info, _newboard)| match info.special {
-                    MoveSpecialInfo::BallPush { ball_to: _ } => true,
-                    _ => false,
-                });
-        assert!(found_push);
+                .filter(|(info, _)| info.special == MoveSpecialInfo::SimpleMove)
+                .all(|(info, _)| info.push_dir.is_none())
+        );
+    }
+
+    #[test]
+    fn test_forbidden_columns_block_pushes() {
+        let pushes_to_column_0 = |b: &ChessBallBoard| {
+            possible_moves(b, Player::White).iter().any(|(mv, _)| {
+                matches!(mv.special, MoveSpecialInfo::BallPush { ball_to } if ball_to.c == 0)
+            })
+        };
+        let standard = BoardBuilder::new()
+            .defender(Player::White, 2, 2)
+            .ball(2, 1)
+            .build()
+            .unwrap();
+        assert!(standard.is_forbidden_col(Coord { r: 2, c: 0 }));
+        assert!(!pushes_to_column_0(&standard));
+
+        let open = BoardBuilder::new()
+            .forbidden_cols(&[])
+            .defender(Player::White, 2, 2)
+            .ball(2, 1)
+            .build()
+            .unwrap();
+        assert_eq!(open.forbidden_cols().count(), 0);
+        assert!(pushes_to_column_0(&open));
+        let (push, after) = possible_moves(&open, Player::White)
+            .into_iter()
+            .find(|(mv, _)| mv.kind() == MoveKind::Push && mv.push_dir == Some((0, -1)))
+            .unwrap();
+        assert_eq!(after.find_ball(), Some(Coord { r: 2, c: 0 }));
+        assert!(
+            possible_previous_moves(&after, Player::White)
+                .iter()
+                .any(|(mv, before)| *mv == push && *before == open)
+        );
+    }
+
+    #[test]
+    fn test_move_kind_matches_special() {
+        let b = BoardBuilder::new()
+            .attacker(Player::White, 2, 2)
+            .defender(Player::Black, 2, 3)
+            .defender(Player::White, 4, 2)
+            .attacker(Player::Black, 4, 3)
+            .ball(1, 3)
+            .build()
+            .unwrap();
+        let moves = move_list(&b, Player::White);
+        let kind = |(fr, fc): (usize, usize), (tr, tc): (usize, usize)| {
+            moves
+                .iter()
+                .find(|mv| mv.from == Coord { r: fr, c: fc } && mv.to == Coord { r: tr, c: tc })
+                .map(MoveInfo::kind)
+        };
+        // The attacker pushes the ball on (1, 3) to (0, 4) and jumps the Black defender;
+        // the White defender tackles the Black attacker east.
+        assert_eq!(kind((2, 2), (1, 3)), Some(MoveKind::Push));
+        assert_eq!(kind((2, 2), (2, 4)), Some(MoveKind::Jump));
+        assert_eq!(kind((4, 2), (4, 3)), Some(MoveKind::Tackle));
+        // Every other move is a step onto one of the 13 free neighbouring squares.
+        assert_eq!(kind((2, 2), (2, 1)), Some(MoveKind::Simple));
+        assert_eq!(kind((4, 2), (5, 3)), Some(MoveKind::Simple));
+        assert_eq!(moves.len(), 16);
+        assert_eq!(
+            moves
+                .iter()
+                .filter(|mv| mv.kind() == MoveKind::Simple)
+                .count(),
+            13
+        );
+    }
+
+    #[test]
+    fn test_is_reversible() {
+        let from = Coord { r: 2, c: 3 };
+        let to = Coord { r: 3, c: 3 };
+        assert!(MoveInfo::simple(from, to).is_reversible());
+        assert!(!MoveInfo::ball_push(from, to, Coord { r: 4, c: 3 }).is_reversible());
+
+        let b = BoardBuilder::new()
+            .attacker(Player::White, 2, 2)
+            .defender(Player::Black, 2, 3)
+            .defender(Player::White, 4, 2)
+            .attacker(Player::Black, 4, 3)
+            .ball(3, 1)
+            .build()
+            .unwrap();
+        let moves = possible_moves(&b, Player::White);
+        let of_kind = |kind| moves.iter().find(|(info, _)| info.kind() == kind).unwrap();
+        assert!(of_kind(MoveKind::Jump).0.is_reversible());
+        assert!(!of_kind(MoveKind::Tackle).0.is_reversible());
+    }
+
+    #[test]
+    fn test_generated_boards_stay_valid() {
+        let mut boards = vec![
+            ChessBallBoard::new_game(),
+            BoardBuilder::new()
+                .attacker(Player::White, 2, 2)
+                .defender(Player::Black, 2, 3)
+                .defender(Player::White, 4, 2)
+                .attacker(Player::Black, 4, 3)
+                .ball(3, 1)
+                .build()
+                .unwrap(),
+        ];
+        // Add the positions two plies into the game.
+        for (_, after) in possible_moves(&ChessBallBoard::new_game(), Player::White) {
+            boards.extend(
+                possible_moves(&after, Player::Black)
+                    .into_iter()
+                    .map(|(_, b)| b),
+            );
+        }
+        for board in &boards {
+            assert_eq!(board.validate(), Ok(()));
+            for player in [Player::White, Player::Black] {
+                for (mv, after) in possible_moves(board, player) {
+                    assert_eq!(after.validate(), Ok(()), "{mv:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_moves_by_source_partitions_the_moves() {
+        let board = ChessBallBoard::new_game();
+        for player in [Player::White, Player::Black] {
+            let groups = moves_by_source(&board, player);
+            let all = move_list(&board, player);
+            assert_eq!(groups.values().map(Vec::len).sum::<usize>(), all.len());
+            let flattened: HashSet<MoveInfo> = groups
+                .iter()
+                .flat_map(|(&(r, c), moves)| {
+                    assert!(moves.iter().all(|mv| mv.from == Coord { r, c }));
+                    moves.iter().cloned()
+                })
+                .collect();
+            assert_eq!(flattened, all.into_iter().collect());
+        }
+    }
+
+    #[test]
+    fn test_orthogonal_defenders_keep_diagonal_attackers() {
+        let board = BoardBuilder::new()
+            .move_rules(MoveRules::orthogonal_defenders())
+            .defender(Player::White, 2, 3)
+            .attacker(Player::White, 2, 1)
+            .ball(4, 5)
+            .defender(Player::Black, 0, 6)
+            .build()
+            .unwrap();
+        let moves = possible_moves(&board, Player::White);
+        let diagonal = |mv: &MoveInfo| mv.from.r != mv.to.r && mv.from.c != mv.to.c;
+        let from = |r, c| {
+            moves
+                .iter()
+                .filter(move |(mv, _)| mv.from == Coord { r, c })
+        };
+
+        assert_eq!(from(2, 3).count(), 4);
+        assert!(!from(2, 3).any(|(mv, _)| diagonal(mv)));
+        assert!(from(2, 1).any(|(mv, _)| diagonal(mv)));
+        // The rules carry over to the boards after each move.
+        assert!(
+            moves
+                .iter()
+                .all(|(_, after)| after.move_rules() == MoveRules::orthogonal_defenders())
+        );
+    }
+
+    #[test]
+    fn test_defensive_tackles_target_pushers() {
+        // White's defender at (2,2) can tackle the attacker next to the ball at (3,3) and
+        // the one at (1,2), which is away from the ball.
+        let board = BoardBuilder::new()
+            .defender(Player::White, 2, 2)
+            .attacker(Player::Black, 3, 3)
+            .attacker(Player::Black, 1, 2)
+            .ball(4, 3)
+            .build()
+            .unwrap();
+        let tackles: Vec<Coord> = possible_moves(&board, Player::White)
+            .into_iter()
+            .filter_map(|(mv, _)| match mv.special {
+                MoveSpecialInfo::DefenderTackle(tackle) => Some(tackle.pushed_piece_from),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(tackles.len(), 2);
+
+        let defensive = defensive_tackles(&board, Player::White);
+        assert_eq!(defensive.len(), 1);
+        let (mv, after) = &defensive[0];
+        assert_eq!(
+            mv.special,
+            MoveSpecialInfo::DefenderTackle(DefenderTackle {
+                pushed_piece_from: Coord { r: 3, c: 3 },
+                pushed_piece_to: Coord { r: 4, c: 4 },
+                chain: Vec::new(),
+            })
+        );
+        assert_eq!(*after, apply_move(&board, mv));
+        assert!(defensive_tackles(&board, Player::Black).is_empty());
+    }
+
+    #[test]
+    fn test_neutral_never_moves() {
+        // The ball sits on White's goal row with free squares around it.
+        let b = BoardBuilder::new()
+            .defender(Player::White, 4, 3)
+            .ball(5, 3)
+            .defender(Player::Black, 0, 0)
+            .build()
+            .unwrap();
+        assert!(possible_moves(&b, Player::Neutral).is_empty());
+        assert!(move_list(&b, Player::Neutral).is_empty());
+        assert!(possible_previous_moves(&b, Player::Neutral).is_empty());
+        assert!(winning_moves(&b, Player::Neutral).is_empty());
+        assert!(!winning_moves(&b, Player::White).is_empty());
+    }
+
+    #[test]
+    fn test_move_info_hash_matches_equality() {
+        let from = Coord { r: 2, c: 3 };
+        let to = Coord { r: 3, c: 3 };
+        let push = |ball_to| MoveInfo::ball_push(from, to, ball_to);
+        let mut set = HashSet::new();
+        assert!(set.insert(MoveInfo::simple(from, to)));
+        assert!(set.insert(push(Coord { r: 4, c: 3 })));
+        assert!(!set.insert(MoveInfo::simple(from, to)));
+        assert_eq!(set.len(), 2);
+        // Same squares, different tactical details.
+        assert_ne!(push(Coord { r: 4, c: 3 }), push(Coord { r: 4, c: 4 }));
+    }
+
+    #[test]
+    fn test_mobility_by_type_at_start() {
+        let start = ChessBallBoard::new_game();
+        // Each attacker has 6 moves and the defenders 4, 3 and 4, except that Black's
+        // attackers sit diagonally next to the ball and push it instead of stepping there.
+        let expected = [(Player::White, (12, 11, 0)), (Player::Black, (10, 11, 2))];
+        for (player, counts) in expected {
+            let (attackers, defenders, pushes) = mobility_by_type(&start, player);
+            assert_eq!((attackers, defenders, pushes), counts, "{player:?}");
+            assert_eq!(
+                attackers + defenders + pushes,
+                move_list(&start, player).len()
+            );
+        }
     }
 
     #[test]
@@ -323,15 +1158,11 @@ mod tests {
 
     #[test]
     fn test_possible_previous_moves() {
-        let mut b = ChessBallBoard::new();
-        b.place_ball(Coord { r: 2, c: 4 });
-        b.place_piece(
-            Coord { r: 2, c: 3 },
-            Piece {
-                piece_type: PieceType::Defender,
-                player: Player::White,
-            },
-        );
+        let b = BoardBuilder::new()
+            .ball(2, 4)
+            .defender(Player::White, 2, 3)
+            .build()
+            .unwrap();
         let prevs = possible_previous_moves(&b, Player::White);
         assert!(!prevs.is_empty());
     }
@@ -340,7 +1171,7 @@ mod tests {
     // We call them through `super::` because they are private to the module.
 
     #[test]
-    fn test_gen_simple_move_for() {
+    fn test_simple_move_for() {
         let mut b = ChessBallBoard::new();
         b.place_piece(
             Coord { r: 2, c: 2 },
@@ -349,29 +1180,22 @@ mod tests {
                 player: Player::White,
             },
         );
-        let mut results = Vec::new();
         // try all directions; expect at least one simple move
-        for &delta in DIRECTIONS.iter() {
-            super::gen_simple_move_for(
-                &b,
-                Player::White,
-                Coord { r: 2, c: 2 },
-                &b.get_piece(Coord { r: 2, c: 2 }).unwrap(),
-                delta,
-                &mut results,
-            );
-        }
+        let results: Vec<MoveInfo> = DIRECTIONS
+            .iter()
+            .filter_map(|&delta| super::simple_move_for(&b, Coord { r: 2, c: 2 }, delta))
+            .collect();
         assert!(!results.is_empty(), "Expected at least one simple move");
         // ensure none of the generated moves have special flags
         assert!(
             results
                 .iter()
-                .all(|(m, _)| m.special == MoveSpecialInfo::SimpleMove)
+                .all(|m| m.special == MoveSpecialInfo::SimpleMove)
         );
     }
 
     #[test]
-    fn test_gen_ball_push_move_for() {
+    fn test_ball_push_move_for() {
         let mut b = ChessBallBoard::new();
         b.place_piece(
             Coord { r: 2, c: 3 },
@@ -382,33 +1206,27 @@ mod tests {
         );
         b.place_ball(Coord { r: 2, c: 4 });
         // ensure (2,5) is empty and not forbidden (board.new() uses standard columns)
-        let mut results = Vec::new();
         // only the rightward direction should produce a push
-        super::gen_ball_push_move_for(
+        let push = super::ball_push_move_for(
             &b,
             Player::White,
             Coord { r: 2, c: 3 },
-            &b.get_piece(Coord { r: 2, c: 3 }).unwrap(),
+            b.get_piece(Coord { r: 2, c: 3 }).unwrap(),
             CoordDelta {
                 r: 0,
                 c: 1,
                 rows: b.rows,
                 cols: b.cols,
             },
-            &mut results,
         );
-        println!("{}", b);
         assert!(
-            results.iter().any(|(info, _)| match info.special {
-                MoveSpecialInfo::BallPush { ball_to: _ } => true,
-                _ => false,
-            }),
+            push.is_some_and(|info| matches!(info.special, MoveSpecialInfo::BallPush { .. })),
             "Expected a ball-push move"
         );
     }
 
     #[test]
-    fn test_gen_attacker_jump_move_for() {
+    fn test_attacker_jump_move_for() {
         let mut b = ChessBallBoard::new();
         // place attacker at (2,2), opponent piece (non-ball) at (2,3) and empty (2,4)
         b.place_piece(
@@ -425,28 +1243,27 @@ mod tests {
                 player: Player::Black,
             },
         );
-        let mut results = Vec::new();
-        super::gen_attacker_jump_move_for(
+        let jump = super::attacker_jump_move_for(
             &b,
-            Player::White,
             Coord { r: 2, c: 2 },
-            &b.get_piece(Coord { r: 2, c: 2 }).unwrap(),
+            b.get_piece(Coord { r: 2, c: 2 }).unwrap(),
             CoordDelta {
                 r: 0,
                 c: 1,
                 rows: b.rows,
                 cols: b.cols,
             },
-            &mut results,
         );
-        assert!(results.iter().any(|(info, _)| info.special
-            == MoveSpecialInfo::AttackerJump {
+        assert_eq!(
+            jump.map(|info| info.special),
+            Some(MoveSpecialInfo::AttackerJump {
                 jumped_over: Coord { r: 2, c: 3 },
-            }));
+            })
+        );
     }
 
     #[test]
-    fn test_gen_defender_tackle_move_for() {
+    fn test_defender_tackle_move_for() {
         let mut b = ChessBallBoard::new();
         // place defender at (2,2), opponent piece at (2,3), empty at (2,4)
         b.place_piece(
@@ -463,24 +1280,310 @@ mod tests {
                 player: Player::Black,
             },
         );
-        let mut results = Vec::new();
-        super::gen_defender_tackle_move_for(
+        let tackle = super::defender_tackle_move_for(
             &b,
             Player::White,
             Coord { r: 2, c: 2 },
-            &b.get_piece(Coord { r: 2, c: 2 }).unwrap(),
+            b.get_piece(Coord { r: 2, c: 2 }).unwrap(),
             CoordDelta {
                 r: 0,
                 c: 1,
                 rows: b.rows,
                 cols: b.cols,
             },
-            &mut results,
         );
-        assert!(results.iter().any(|(m, _)| m.special
-            == MoveSpecialInfo::DefenderTackle(DefenderTackle {
+        assert_eq!(
+            tackle.map(|m| m.special),
+            Some(MoveSpecialInfo::DefenderTackle(DefenderTackle {
                 pushed_piece_from: Coord { r: 2, c: 3 },
                 pushed_piece_to: Coord { r: 2, c: 4 },
-            })));
+                chain: Vec::new(),
+            }))
+        );
+    }
+
+    /// White defender on (2, 1) facing Black pieces on (2, 2) and (2, 3), with chain
+    /// tackles enabled.
+    fn chain_board() -> BoardBuilder {
+        BoardBuilder::new()
+            .move_rules(MoveRules::default().with_chain_tackle(true))
+            .defender(Player::White, 2, 1)
+            .attacker(Player::Black, 2, 2)
+            .defender(Player::Black, 2, 3)
+            .ball(4, 3)
+    }
+
+    fn tackles_east(board: &ChessBallBoard) -> Vec<(MoveInfo, ChessBallBoard)> {
+        possible_moves(board, Player::White)
+            .into_iter()
+            .filter(|(mv, _)| mv.kind() == MoveKind::Tackle && mv.to == Coord { r: 2, c: 2 })
+            .collect()
+    }
+
+    #[test]
+    fn test_chain_tackle_shoves_two_pieces() {
+        let board = chain_board().build().unwrap();
+        let tackles = tackles_east(&board);
+        assert_eq!(tackles.len(), 1);
+        let (mv, after) = &tackles[0];
+        assert_eq!(
+            mv.special,
+            MoveSpecialInfo::DefenderTackle(DefenderTackle {
+                pushed_piece_from: Coord { r: 2, c: 2 },
+                pushed_piece_to: Coord { r: 2, c: 3 },
+                chain: vec![Coord { r: 2, c: 3 }],
+            })
+        );
+        let at = |c| {
+            after
+                .get_piece(Coord { r: 2, c })
+                .map(|p| (p.player, p.piece_type))
+        };
+        assert_eq!(at(1), None);
+        assert_eq!(at(2), Some((Player::White, PieceType::Defender)));
+        assert_eq!(at(3), Some((Player::Black, PieceType::Attacker)));
+        assert_eq!(at(4), Some((Player::Black, PieceType::Defender)));
+        assert!(
+            possible_previous_moves(after, Player::White)
+                .iter()
+                .any(|(prev_mv, prev)| prev_mv == mv && *prev == board)
+        );
+
+        // Without the rule the second piece blocks the tackle.
+        let mut single = board.clone();
+        single.set_move_rules(MoveRules::default());
+        assert!(tackles_east(&single).is_empty());
+    }
+
+    #[test]
+    fn test_chain_tackle_blocked() {
+        // An own piece behind the line blocks it, and so does the board edge.
+        let own_behind = chain_board().attacker(Player::White, 2, 4).build().unwrap();
+        assert!(tackles_east(&own_behind).is_empty());
+
+        let to_the_edge = chain_board()
+            .attacker(Player::Black, 2, 4)
+            .defender(Player::Black, 2, 5)
+            .defender(Player::Black, 2, 6)
+            .build();
+        assert!(tackles_east(&to_the_edge.unwrap()).is_empty());
+    }
+
+    #[test]
+    fn test_possible_moves_into_reuses_dirty_buffer() {
+        let start = ChessBallBoard::new_game();
+        let mut buffer = MoveList::new();
+        possible_moves_into(&start, Player::Black, &mut buffer);
+        let (_, after) = buffer[0].clone();
+        assert!(!buffer.is_empty());
+
+        possible_moves_into(&after, Player::White, &mut buffer);
+        assert_eq!(buffer, possible_moves(&after, Player::White));
+    }
+
+    #[test]
+    fn test_move_list_matches_possible_moves() {
+        let mut board = ChessBallBoard::new_game();
+        let mut player = Player::White;
+        for ply in 0..8 {
+            let full = possible_moves(&board, player);
+            let moves = move_list(&board, player);
+            assert_eq!(moves.len(), full.len());
+            for (mv, (full_mv, full_board)) in moves.iter().zip(&full) {
+                assert_eq!(mv, full_mv);
+                assert_eq!(&apply_move(&board, mv), full_board);
+            }
+            board = full[(ply * 3 + 2) % full.len()].1.clone();
+            player = player.opponent();
+        }
+    }
+
+    #[test]
+    fn test_perft_counts_leaves() {
+        let board = ChessBallBoard::new_game();
+        assert_eq!(perft(&board, Player::White, 0), 1);
+        let moves = possible_moves(&board, Player::White);
+        assert_eq!(perft(&board, Player::White, 1), moves.len() as u64);
+        let depth_two: u64 = moves
+            .iter()
+            .map(|(_, after)| possible_moves(after, Player::Black).len() as u64)
+            .sum();
+        assert_eq!(perft(&board, Player::White, 2), depth_two);
+    }
+
+    /// Play `choices` (each taken modulo the number of legal moves) from the start
+    /// position, stopping early once the ball reaches a goal row.
+    fn random_position(choices: &[usize]) -> (ChessBallBoard, Player) {
+        let mut board = ChessBallBoard::new_game();
+        let mut player = Player::White;
+        for &choice in choices {
+            let moves = possible_moves(&board, player);
+            if moves.is_empty()
+                || board
+                    .find_ball()
+                    .is_some_and(|ball| ball.r == 0 || ball.r == board.rows - 1)
+            {
+                break;
+            }
+            board = moves[choice % moves.len()].1.clone();
+            player = player.opponent();
+        }
+        (board, player)
+    }
+
+    proptest! {
+        #[test]
+        fn prop_previous_moves_recover_the_position(
+            choices in prop::collection::vec(any::<usize>(), 1..30)
+        ) {
+            let (board, player) = random_position(&choices[..choices.len() - 1]);
+            let moves = possible_moves(&board, player);
+            prop_assume!(!moves.is_empty());
+            let (mv, after) = &moves[choices[choices.len() - 1] % moves.len()];
+
+            let mut original = board.clone();
+            original.prev_tackle = None;
+            let prevs = possible_previous_moves(after, player);
+            prop_assert!(
+                prevs.iter().any(|(prev_mv, prev)| prev_mv == mv && *prev == original),
+                "{:?} from\n{}not recovered from\n{}", mv, board, after
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_round_trips_every_legal_move() {
+        let mut boards = vec![ChessBallBoard::new_game()];
+        boards.extend(
+            possible_moves(&boards[0], Player::White)
+                .into_iter()
+                .map(|(_, b)| b),
+        );
+        for board in &boards {
+            for player in [Player::White, Player::Black] {
+                let moves = move_list(board, player);
+                let codes: HashSet<u32> = moves.iter().map(|mv| mv.encode(board)).collect();
+                assert_eq!(codes.len(), moves.len());
+                for mv in &moves {
+                    assert_eq!(MoveInfo::decode(mv.encode(board), board).as_ref(), Some(mv));
+                }
+            }
+        }
+        assert_eq!(MoveInfo::decode(u32::MAX, &boards[0]), None);
+    }
+
+    #[test]
+    fn test_pass_move_only_swaps_the_turn() {
+        let board = ChessBallBoard::new_game();
+        let (mv, passed, to_move) = pass_move(&board, Player::White);
+        assert!(mv.is_pass());
+        assert_eq!(passed, board);
+        assert_eq!(to_move, Player::Black);
+        assert!(
+            possible_moves(&board, Player::White)
+                .iter()
+                .all(|(mv, _)| !mv.is_pass())
+        );
+    }
+
+    #[test]
+    fn test_describe_each_kind() {
+        let board = BoardBuilder::new()
+            .defender(Player::White, 2, 1)
+            .attacker(Player::Black, 2, 2)
+            .attacker(Player::White, 3, 4)
+            .defender(Player::Black, 3, 3)
+            .ball(4, 4)
+            .build()
+            .unwrap();
+        let moves = possible_moves(&board, Player::White);
+        let described = |from: Coord, to: Coord, kind: MoveKind| {
+            let (mv, _) = moves
+                .iter()
+                .find(|(mv, _)| mv.from == from && mv.to == to && mv.kind() == kind)
+                .unwrap_or_else(|| panic!("no {kind:?} move {from:?} -> {to:?}"));
+            mv.describe(&board)
+        };
+
+        assert_eq!(
+            described(Coord { r: 2, c: 1 }, Coord { r: 1, c: 1 }, MoveKind::Simple),
+            "White defender moves from b4 to b5"
+        );
+        assert_eq!(
+            described(Coord { r: 3, c: 4 }, Coord { r: 4, c: 4 }, MoveKind::Push),
+            "White attacker moves from e3 to e2, pushing the ball to e1"
+        );
+        assert_eq!(
+            described(Coord { r: 3, c: 4 }, Coord { r: 3, c: 2 }, MoveKind::Jump),
+            "White attacker jumps from e3 over the Black defender on d3 to c3"
+        );
+        assert_eq!(
+            described(Coord { r: 2, c: 1 }, Coord { r: 2, c: 2 }, MoveKind::Tackle),
+            "White defender tackles the Black attacker on c4, pushing it to d4"
+        );
+        assert_eq!(MoveInfo::pass().describe(&board), "Pass");
+    }
+
+    #[test]
+    fn test_legal_push_dirs_two_directions() {
+        let board = BoardBuilder::new()
+            .defender(Player::White, 1, 2)
+            .attacker(Player::White, 1, 3)
+            .ball(2, 3)
+            .defender(Player::Black, 3, 2)
+            .build()
+            .unwrap();
+        // Straight down and down-right for White; Black's defender pushes up-right.
+        assert_eq!(legal_push_dirs(&board, Player::White), vec![(1, 0), (1, 1)]);
+        assert_eq!(legal_push_dirs(&board, Player::Black), vec![(-1, 1)]);
+
+        let mut blocked = board.clone();
+        blocked.place_piece(
+            Coord { r: 3, c: 4 },
+            Piece {
+                piece_type: PieceType::Defender,
+                player: Player::Black,
+            },
+        );
+        assert_eq!(legal_push_dirs(&blocked, Player::White), vec![(1, 0)]);
+    }
+
+    #[test]
+    fn test_moves_follow_the_board_size() {
+        // Squares beyond the default 6x7 bounds are reachable on a larger board...
+        let large = BoardBuilder::new()
+            .size(9, 10)
+            .defender(Player::White, 7, 8)
+            .ball(4, 4)
+            .build()
+            .unwrap();
+        let targets: HashSet<Coord> = move_list(&large, Player::White)
+            .into_iter()
+            .filter(|mv| mv.from == Coord { r: 7, c: 8 })
+            .map(|mv| mv.to)
+            .collect();
+        assert_eq!(targets.len(), 8);
+        assert!(targets.contains(&Coord { r: 8, c: 9 }));
+        assert!(
+            possible_previous_moves(&large, Player::White)
+                .iter()
+                .any(|(mv, _)| mv.to == Coord { r: 7, c: 8 } && mv.from == Coord { r: 8, c: 9 })
+        );
+
+        // ...and a smaller board is never left.
+        let small = BoardBuilder::new()
+            .size(3, 4)
+            .defender(Player::White, 2, 3)
+            .attacker(Player::Black, 0, 0)
+            .ball(1, 1)
+            .build()
+            .unwrap();
+        for player in [Player::White, Player::Black] {
+            for (mv, after) in possible_moves(&small, player) {
+                assert!(small.is_on_board(mv.to), "{mv}");
+                assert_eq!((after.rows, after.cols), (3, 4));
+            }
+        }
+        assert_eq!(move_list(&small, Player::White).len(), 3);
     }
 }
@@ -11,6 +11,7 @@ use crate::{
 
 pub type StateId = u32;
 type LossCounter = u8;
+type ProofLine = Vec<(Position, Outcome, Option<Move>)>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Outcome {
@@ -542,10 +543,7 @@ impl PartialTablebaseResult {
     }
 
     #[must_use]
-    fn proof_path_from_start_to(
-        &self,
-        target: StateId,
-    ) -> (Vec<StateId>, Vec<(Position, Outcome, Option<Move>)>, usize) {
+    fn proof_path_from_start_to(&self, target: StateId) -> (Vec<StateId>, ProofLine, usize) {
         let mut prefix_states = self.state_chain_to(target);
         let mut prefix_line = self.path_from_start_to(target);
         let (mut proof_states, mut proof_line) = self.proof_trace_from(target);
@@ -564,10 +562,7 @@ impl PartialTablebaseResult {
     }
 
     #[must_use]
-    fn proof_trace_from(
-        &self,
-        start: StateId,
-    ) -> (Vec<StateId>, Vec<(Position, Outcome, Option<Move>)>) {
+    fn proof_trace_from(&self, start: StateId) -> (Vec<StateId>, ProofLine) {
         let mut states = Vec::new();
         let mut line = Vec::new();
         let mut seen = HashSet::new();
@@ -1247,10 +1242,10 @@ fn proof_paths_dot(paths: &[CertifiedPath]) -> String {
                 .entry(state)
                 .and_modify(|entry| entry.2 |= is_target)
                 .or_insert((position, outcome, is_target));
-            if let Some((_, _, Some(mv))) = path.line.get(index) {
-                if let Some(&next) = path.states.get(index + 1) {
-                    edges.push((state, next, *mv));
-                }
+            if let Some((_, _, Some(mv))) = path.line.get(index)
+                && let Some(&next) = path.states.get(index + 1)
+            {
+                edges.push((state, next, *mv));
             }
         }
     }
@@ -0,0 +1,185 @@
+//! Python bindings via PyO3 (enabled with the `pyo3` feature).
+//!
+//! Exposes a `chessball` Python module with a `Board` class, a `Move` class and a
+//! `best_move(board, player, depth)` function. Players are passed as their
+//! single-letter initial (`"W"` or `"B"`), as in the textual board format.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::board::{ChessBallBoard, Coord, Player};
+use crate::minimax::choose_best_move;
+use crate::moves::{MoveInfo, MoveSpecialInfo, possible_moves};
+
+/// Python wrapper around `ChessBallBoard`.
+#[pyclass(name = "Board", module = "chessball")]
+#[derive(Debug, Clone)]
+pub struct PyBoard {
+    board: ChessBallBoard,
+}
+
+/// Python wrapper around `MoveInfo`.
+#[pyclass(name = "Move", module = "chessball")]
+#[derive(Debug, Clone)]
+pub struct PyMove {
+    mv: MoveInfo,
+}
+
+#[pymethods]
+impl PyBoard {
+    /// Parse a board from its textual representation.
+    #[new]
+    fn new(repr: &str) -> PyResult<Self> {
+        ChessBallBoard::from_repr(repr)
+            .map(|board| Self { board })
+            .map_err(PyValueError::new_err)
+    }
+
+    /// The standard starting position.
+    #[staticmethod]
+    fn new_game() -> Self {
+        Self {
+            board: ChessBallBoard::new_game(),
+        }
+    }
+
+    #[getter]
+    fn rows(&self) -> usize {
+        self.board.rows
+    }
+
+    #[getter]
+    fn cols(&self) -> usize {
+        self.board.cols
+    }
+
+    /// Ball position as `(row, col)`, or `None`.
+    fn find_ball(&self) -> Option<(usize, usize)> {
+        self.board.find_ball().map(|at| (at.r, at.c))
+    }
+
+    /// Legal moves of `player`, in the order produced by `possible_moves`.
+    fn legal_moves(&self, player: &str) -> PyResult<Vec<PyMove>> {
+        let player = parse_player(player)?;
+        Ok(possible_moves(&self.board, player)
+            .into_iter()
+            .map(|(mv, _)| PyMove { mv })
+            .collect())
+    }
+
+    /// Return the board after `player` plays `mv`; raises `ValueError` if illegal.
+    fn apply_move(&self, player: &str, mv: &PyMove) -> PyResult<Self> {
+        let player = parse_player(player)?;
+        possible_moves(&self.board, player)
+            .into_iter()
+            .find(|(candidate, _)| *candidate == mv.mv)
+            .map(|(_, board)| Self { board })
+            .ok_or_else(|| PyValueError::new_err(format!("illegal move {}", mv.mv)))
+    }
+
+    fn __str__(&self) -> String {
+        self.board.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Board({:?})", self.board.to_string())
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self.board == other.board
+    }
+}
+
+#[pymethods]
+impl PyMove {
+    #[getter(from_square)]
+    fn source(&self) -> (usize, usize) {
+        coord_tuple(self.mv.from)
+    }
+
+    #[getter(to_square)]
+    fn destination(&self) -> (usize, usize) {
+        coord_tuple(self.mv.to)
+    }
+
+    /// One of `"simple"`, `"push"`, `"jump"` or `"tackle"`.
+    #[getter]
+    fn kind(&self) -> &'static str {
+        match self.mv.special {
+            MoveSpecialInfo::SimpleMove => "simple",
+            MoveSpecialInfo::BallPush { .. } => "push",
+            MoveSpecialInfo::AttackerJump { .. } => "jump",
+            MoveSpecialInfo::DefenderTackle(_) => "tackle",
+        }
+    }
+
+    fn __str__(&self) -> String {
+        self.mv.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Move({}, kind={})", self.mv, self.kind())
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self.mv == other.mv
+    }
+}
+
+/// Search `depth` plies and return `(move, board_after, score)`, or `None` when no
+/// move was found.
+#[pyfunction]
+fn best_move(
+    board: &PyBoard,
+    player: &str,
+    depth: usize,
+) -> PyResult<Option<(PyMove, PyBoard, f64)>> {
+    let player = parse_player(player)?;
+    let (mv, board_after, score) = choose_best_move(&board.board, player, depth);
+    Ok(mv
+        .zip(board_after)
+        .map(|(mv, board)| (PyMove { mv }, PyBoard { board }, score)))
+}
+
+/// The `chessball` Python module.
+#[pymodule]
+fn chessball(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyBoard>()?;
+    m.add_class::<PyMove>()?;
+    m.add_function(wrap_pyfunction!(best_move, m)?)?;
+    Ok(())
+}
+
+fn parse_player(player: &str) -> PyResult<Player> {
+    Player::from_initial(player)
+        .ok_or_else(|| PyValueError::new_err(format!("invalid player '{player}'")))
+}
+
+fn coord_tuple(at: Coord) -> (usize, usize) {
+    (at.r, at.c)
+}
+
+#[cfg(test)]
+mod tests {
+    use pyo3::prelude::*;
+    use pyo3::types::PyModule;
+
+    use super::{PyBoard, best_move, chessball};
+
+    #[test]
+    fn test_module_objects_and_best_move() {
+        Python::initialize();
+        Python::attach(|py| {
+            let module = PyModule::new(py, "chessball").unwrap();
+            chessball(&module).unwrap();
+            assert!(module.getattr("Board").is_ok());
+            assert!(module.getattr("Move").is_ok());
+
+            let board = PyBoard::new_game();
+            assert!(!board.legal_moves("W").unwrap().is_empty());
+            let (mv, after, _score) = best_move(&board, "W", 1).unwrap().unwrap();
+            assert_eq!(board.apply_move("W", &mv).unwrap().board, after.board);
+            assert!(board.legal_moves("N").is_err());
+        });
+    }
+}
@@ -1,5 +1,6 @@
-use std::fmt;
+use std::{fmt, fs, io, path::Path};
 
+use crate::board::Coord;
 use crate::engine::{COLS, Move, MoveKind, Player, Position, ROWS, Square, square};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -62,6 +63,8 @@ pub enum Termination {
     ThreefoldRepetition,
     PlyLimit,
     NoLegalMoves,
+    InsufficientMaterial,
+    DeadPosition,
 }
 
 impl Termination {
@@ -72,6 +75,8 @@ impl Termination {
             Self::ThreefoldRepetition => "threefold-repetition",
             Self::PlyLimit => "ply-limit",
             Self::NoLegalMoves => "no-legal-moves",
+            Self::InsufficientMaterial => "insufficient-material",
+            Self::DeadPosition => "dead-position",
         }
     }
 
@@ -81,11 +86,16 @@ impl Termination {
             "threefold-repetition" => Ok(Self::ThreefoldRepetition),
             "ply-limit" => Ok(Self::PlyLimit),
             "no-legal-moves" => Ok(Self::NoLegalMoves),
+            "insufficient-material" => Ok(Self::InsufficientMaterial),
+            "dead-position" => Ok(Self::DeadPosition),
             other => Err(format!("unknown termination '{other}'")),
         }
     }
 }
 
+/// Plies after which a win is worth half of an immediate one in `GameRecord::score_for`.
+const WIN_SPEED_PLIES: f64 = 100.0;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct PlyRecord {
     pub player: Player,
@@ -107,6 +117,29 @@ pub struct GameRecord {
 }
 
 impl GameRecord {
+    /// Plies played from the initial position until the game ended.
+    #[must_use]
+    pub fn plies_to_result(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// The game's score for `player`, for tuning by self-play: a win is worth
+    /// `1 / (1 + plies / 100)`, so faster wins score closer to +1, a loss is -1 and a
+    /// draw 0.
+    #[must_use]
+    pub fn score_for(&self, player: Player) -> f64 {
+        let winner = match self.outcome {
+            GameOutcome::WhiteWin => Player::White,
+            GameOutcome::BlackWin => Player::Black,
+            GameOutcome::Draw => return 0.0,
+        };
+        if winner == player {
+            1.0 / (1.0 + self.plies_to_result() as f64 / WIN_SPEED_PLIES)
+        } else {
+            -1.0
+        }
+    }
+
     #[must_use]
     pub fn to_text(&self) -> String {
         let mut out = String::new();
@@ -217,6 +250,17 @@ impl GameRecord {
         })
     }
 
+    /// Write the record to `path` in the `CBR1` text format.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_text())
+    }
+
+    /// Read a record written by [`GameRecord::save`], replaying and validating its moves.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Self::from_text(&content).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
     pub fn replay_positions(&self) -> Result<Vec<Position>, String> {
         let mut states = vec![self.initial_position];
         let mut position = self.initial_position;
@@ -239,6 +283,149 @@ impl GameRecord {
         }
         Ok(states)
     }
+
+    /// Render a PGN-like transcript: tag pairs, numbered move pairs and the result.
+    ///
+    /// Moves use [`move_to_notation`] (`@` push, `^` jump, `!` tackle) and the move
+    /// that puts the ball on a goal row carries a trailing `#`. A `Setup` tag holds
+    /// the initial board (rows separated by `/`) and side to move when the game did
+    /// not start from `Position::new_game()`. Move sources, scores, node counts and
+    /// the initial tackle memory are not part of the transcript.
+    #[must_use]
+    pub fn to_notation(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&format!("[White \"{}\"]\n", self.white_label));
+        out.push_str(&format!("[Black \"{}\"]\n", self.black_label));
+        if self.initial_position != Position::new_game() {
+            out.push_str(&format!(
+                "[Setup \"{}\"]\n",
+                position_to_setup(&self.initial_position)
+            ));
+        }
+        out.push_str(&format!("[Result \"{}\"]\n", self.outcome.as_result_str()));
+        out.push_str(&format!(
+            "[Termination \"{}\"]\n",
+            self.termination.as_str()
+        ));
+        out.push('\n');
+
+        let mut position = self.initial_position;
+        for (idx, pair) in self.moves.chunks(2).enumerate() {
+            let mut entries = Vec::with_capacity(2);
+            for ply in pair {
+                position = position.apply(ply.mv);
+                let marker = if position.winner().is_some() { "#" } else { "" };
+                entries.push(format!(
+                    "{} {}{marker}",
+                    ply.player.to_char(),
+                    move_to_notation(ply.mv)
+                ));
+            }
+            out.push_str(&format!("{}. {}\n", idx + 1, entries.join("  ")));
+        }
+        out.push_str(self.outcome.as_result_str());
+        out.push('\n');
+        out
+    }
+
+    /// Parse a transcript written by [`GameRecord::to_notation`].
+    ///
+    /// Moves are replayed and validated; parsed plies are marked as
+    /// `MoveSource::Search` without score or node counts.
+    pub fn from_notation(input: &str) -> Result<Self, String> {
+        let mut white_label = None;
+        let mut black_label = None;
+        let mut setup = None;
+        let mut result_tag = None;
+        let mut termination = None;
+        let mut result_line = None;
+        let mut moves = Vec::new();
+        let mut marked_win = false;
+
+        for line in input.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            if let Some(tag) = line.strip_prefix('[') {
+                let (key, value) = tag
+                    .strip_suffix("\"]")
+                    .and_then(|tag| tag.split_once(" \""))
+                    .ok_or_else(|| format!("invalid tag line '{line}'"))?;
+                match key {
+                    "White" => white_label = Some(value.to_string()),
+                    "Black" => black_label = Some(value.to_string()),
+                    "Setup" => setup = Some(parse_setup(value)?),
+                    "Result" => result_tag = Some(GameOutcome::parse(value)?),
+                    "Termination" => termination = Some(Termination::parse(value)?),
+                    other => return Err(format!("unknown tag '{other}'")),
+                }
+                continue;
+            }
+            if let Ok(outcome) = GameOutcome::parse(line) {
+                result_line = Some(outcome);
+                continue;
+            }
+            if result_line.is_some() {
+                return Err(format!("unexpected line after result: '{line}'"));
+            }
+
+            let mut tokens = line.split_whitespace();
+            let number = tokens.next().unwrap_or_default();
+            if !number.ends_with('.') {
+                return Err(format!("missing move number in '{line}'"));
+            }
+            while let Some(side) = tokens.next() {
+                if marked_win {
+                    return Err(format!("move after winning move in '{line}'"));
+                }
+                let player = parse_player_char(side)?;
+                let raw = tokens
+                    .next()
+                    .ok_or_else(|| format!("missing move after '{side}' in '{line}'"))?;
+                let (raw, is_win) = match raw.strip_suffix('#') {
+                    Some(raw) => (raw, true),
+                    None => (raw, false),
+                };
+                marked_win = is_win;
+                moves.push(PlyRecord {
+                    player,
+                    mv: move_from_notation(raw)?,
+                    source: MoveSource::Search,
+                    score: None,
+                    nodes: None,
+                });
+            }
+        }
+
+        let outcome = match (result_tag, result_line) {
+            (Some(tag), Some(line)) if tag != line => {
+                return Err("Result tag does not match the final result".to_string());
+            }
+            (Some(outcome), _) | (None, Some(outcome)) => outcome,
+            (None, None) => return Err("missing result".to_string()),
+        };
+        let termination = termination.ok_or_else(|| "missing Termination tag".to_string())?;
+        let initial_position = setup.unwrap_or_else(Position::new_game);
+        let final_position = replay_moves(&initial_position, &moves)?;
+        if marked_win != final_position.winner().is_some() {
+            return Err("win marker does not match the final position".to_string());
+        }
+        let inferred_outcome = match final_position.winner() {
+            Some(Player::White) => GameOutcome::WhiteWin,
+            Some(Player::Black) => GameOutcome::BlackWin,
+            None => GameOutcome::Draw,
+        };
+        if termination == Termination::GoalRow && inferred_outcome != outcome {
+            return Err("goal-row result does not match final position".to_string());
+        }
+
+        Ok(Self {
+            initial_position,
+            white_label: white_label.ok_or_else(|| "missing White tag".to_string())?,
+            black_label: black_label.ok_or_else(|| "missing Black tag".to_string())?,
+            moves,
+            outcome,
+            termination,
+            final_position,
+        })
+    }
 }
 
 #[must_use]
@@ -296,30 +483,17 @@ pub fn move_from_notation(input: &str) -> Result<Move, String> {
 
 #[must_use]
 pub fn square_to_algebraic(square: Square) -> String {
-    let file = (b'a' + square.col() as u8) as char;
-    let rank = ROWS - square.row();
-    format!("{file}{rank}")
+    Coord {
+        r: square.row(),
+        c: square.col(),
+    }
+    .algebraic(ROWS)
 }
 
 pub fn parse_square_algebraic(input: &str) -> Result<Square, String> {
-    if input.len() != 2 {
-        return Err(format!("invalid square '{input}'"));
-    }
-    let mut chars = input.chars();
-    let file = chars.next().unwrap().to_ascii_lowercase();
-    let rank = chars.next().unwrap();
-    if !(('a'..='g').contains(&file)) {
-        return Err(format!("invalid file in square '{input}'"));
-    }
-    let rank = rank
-        .to_digit(10)
-        .ok_or_else(|| format!("invalid rank in square '{input}'"))? as usize;
-    if !(1..=ROWS).contains(&rank) {
-        return Err(format!("invalid rank in square '{input}'"));
-    }
-    let col = (file as u8 - b'a') as usize;
-    let row = ROWS - rank;
-    Ok(square(row, col))
+    Coord::from_algebraic(input, ROWS, COLS)
+        .map(|at| square(at.r, at.c))
+        .ok_or_else(|| format!("invalid square '{input}'"))
 }
 
 fn parse_prefixed<'a>(line: &'a str, prefix: &str) -> Result<&'a str, String> {
@@ -344,6 +518,22 @@ fn parse_player_char(input: &str) -> Result<Player, String> {
     }
 }
 
+fn position_to_setup(position: &Position) -> String {
+    let board = format!("{position}");
+    format!(
+        "{} {}",
+        board.lines().collect::<Vec<_>>().join("/"),
+        position.to_move.to_char()
+    )
+}
+
+fn parse_setup(input: &str) -> Result<Position, String> {
+    let (board, to_move) = input
+        .rsplit_once(' ')
+        .ok_or_else(|| format!("invalid setup '{input}'"))?;
+    Position::from_repr(&board.replace('/', "\n"), parse_player_char(to_move)?)
+}
+
 fn take_board_block(lines: &[&str], idx: &mut usize) -> Result<String, String> {
     let mut board = String::new();
     for _ in 0..ROWS {
@@ -435,7 +625,13 @@ impl fmt::Display for Termination {
 
 #[cfg(test)]
 mod tests {
+    use std::{
+        env, fs,
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
     use crate::engine::{Move, MoveKind, Piece, PieceKind, Player, Position, square};
+    use crate::tournament::play_selfplay_game;
 
     use super::{
         GameOutcome, GameRecord, MoveSource, PlyRecord, Termination, move_from_notation,
@@ -529,4 +725,118 @@ mod tests {
         assert_eq!(positions.len(), 2);
         assert_eq!(*positions.last().unwrap(), final_position);
     }
+
+    #[test]
+    fn game_record_save_and_load_round_trip() {
+        let record = play_selfplay_game(1, 6, 2, 7, 0);
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let path = env::temp_dir().join(format!(
+            "chessball_record_{}_{}.cbr",
+            std::process::id(),
+            unique
+        ));
+
+        record.save(&path).unwrap();
+        let loaded = GameRecord::load(&path);
+        fs::remove_file(&path).ok();
+
+        assert_eq!(loaded.unwrap(), record);
+    }
+
+    #[test]
+    fn game_record_load_rejects_malformed_file() {
+        let path = env::temp_dir().join(format!("chessball_record_bad_{}.cbr", std::process::id()));
+        fs::write(&path, "not a record\n").unwrap();
+        let err = GameRecord::load(&path).unwrap_err();
+        fs::remove_file(&path).ok();
+
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn notation_round_trip_over_selfplay_game() {
+        let record = play_selfplay_game(1, 10, 4, 11, 0);
+        let text = record.to_notation();
+        assert!(text.starts_with("[White \"solver(depth=1)\"]\n"));
+        assert!(text.contains("\n1. W "));
+        assert!(!text.contains("[Setup"));
+
+        let parsed = GameRecord::from_notation(&text).unwrap();
+        assert_eq!(parsed.to_notation(), text);
+        assert_eq!(parsed.initial_position, record.initial_position);
+        assert_eq!(parsed.final_position, record.final_position);
+        assert_eq!(parsed.outcome, record.outcome);
+        assert_eq!(parsed.termination, record.termination);
+        assert_eq!(
+            parsed.moves.iter().map(|ply| ply.mv).collect::<Vec<_>>(),
+            record.moves.iter().map(|ply| ply.mv).collect::<Vec<_>>()
+        );
+    }
+
+    /// White to move with a single defender, which wins at once by pushing the ball from
+    /// d2 to d1; the record holds that one ply.
+    fn one_push_win_record() -> GameRecord {
+        let mut initial = Position::empty(square(4, 3), Player::White);
+        initial.put_piece(
+            square(3, 3),
+            Piece {
+                player: Player::White,
+                kind: PieceKind::Defender,
+            },
+        );
+        let winning_move = Move {
+            from: square(3, 3),
+            to: square(4, 3),
+            kind: MoveKind::Push {
+                ball_to: square(5, 3),
+            },
+        };
+        GameRecord {
+            initial_position: initial,
+            white_label: "w".to_string(),
+            black_label: "b".to_string(),
+            moves: vec![PlyRecord {
+                player: Player::White,
+                mv: winning_move,
+                source: MoveSource::Search,
+                score: None,
+                nodes: None,
+            }],
+            outcome: GameOutcome::WhiteWin,
+            termination: Termination::GoalRow,
+            final_position: initial.apply(winning_move),
+        }
+    }
+
+    #[test]
+    fn notation_marks_winning_move_and_custom_setup() {
+        let record = one_push_win_record();
+
+        let text = record.to_notation();
+        assert!(text.contains("[Setup \""));
+        assert!(text.contains("1. W d3-d2@d1#\n1-0\n"));
+        assert_eq!(GameRecord::from_notation(&text).unwrap(), record);
+    }
+
+    #[test]
+    fn score_for_rewards_fast_wins() {
+        let mut record = one_push_win_record();
+        assert_eq!(record.plies_to_result(), 1);
+        let fast = record.score_for(Player::White);
+        assert!(fast > 0.99 && fast < 1.0);
+        assert_eq!(record.score_for(Player::Black), -1.0);
+
+        // The same win, had it taken 100 plies, is worth half as much.
+        record.moves = vec![record.moves[0]; 100];
+        assert_eq!(record.score_for(Player::White), 0.5);
+
+        let drawn = play_selfplay_game(1, 4, 0, 3, 0);
+        assert_eq!(drawn.outcome, GameOutcome::Draw);
+        assert_eq!(drawn.plies_to_result(), 4);
+        assert_eq!(drawn.score_for(Player::White), 0.0);
+        assert_eq!(drawn.score_for(Player::Black), 0.0);
+    }
 }
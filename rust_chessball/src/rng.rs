@@ -0,0 +1,52 @@
+//! Small seeded random number generation, so randomized helpers stay reproducible
+//! without an external crate.
+
+/// A source of random numbers, e.g. for `ChessBallBoard::random`.
+pub trait Rng {
+    fn next_u64(&mut self) -> u64;
+
+    /// A uniformly chosen index below `len`. Panics if `len` is 0.
+    fn choose_index(&mut self, len: usize) -> usize {
+        assert!(len > 0);
+        (self.next_u64() as usize) % len
+    }
+}
+
+/// The xorshift64 generator: fast, seedable and good enough for tests and self-play.
+///
+/// Example:
+/// ```
+/// use chessball::rng::{Rng, XorShift64};
+/// let (mut a, mut b) = (XorShift64::new(9), XorShift64::new(9));
+/// assert_eq!(a.next_u64(), b.next_u64());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct XorShift64 {
+    state: u64,
+}
+
+impl XorShift64 {
+    /// A generator seeded with `seed`. Seed 0, a fixed point of xorshift, is replaced
+    /// by a non-zero constant.
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: if seed == 0 {
+                0xA5A5_A5A5_A5A5_A5A5
+            } else {
+                seed
+            },
+        }
+    }
+}
+
+impl Rng for XorShift64 {
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+}
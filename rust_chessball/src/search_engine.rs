@@ -0,0 +1,451 @@
+//! `Engine`: one value bundling what a legacy-board search needs.
+//!
+//! `minimax::choose_best_move` takes its options as loose parameters and always uses the
+//! unweighted `evaluate`. `Engine` keeps the evaluation weights, a transposition table,
+//! the search limits and an optional seeded RNG together, and searches with the same
+//! terminal rules as `minimax::search`.
+
+use std::collections::HashMap;
+use std::hash::{BuildHasher, RandomState};
+use std::time::{Duration, Instant};
+
+use crate::board::{ChessBallBoard, Player};
+use crate::heuristics::{HeuristicWeights, evaluate_for, evaluate_with};
+use crate::minimax::{has_immediate_win, win_score};
+use crate::moves::{MoveInfo, possible_moves};
+use crate::rng::{Rng, XorShift64};
+
+/// Nodes searched between two looks at the clock.
+const TIME_CHECK_INTERVAL: u64 = 1024;
+
+/// How far and how long `Engine::best_move` searches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchLimits {
+    /// Deepest iteration, in plies. Depth 0 is searched as 1, so a move is always found.
+    pub depth: usize,
+    /// Wall-clock budget for the whole search.
+    pub time: Option<Duration>,
+    /// Node budget for the whole search.
+    pub nodes: Option<u64>,
+}
+
+impl Default for SearchLimits {
+    fn default() -> Self {
+        Self {
+            depth: 2,
+            time: None,
+            nodes: None,
+        }
+    }
+}
+
+/// Minimax scores of searched positions, reused across iterations and searches.
+///
+/// Scores depend on the player they are computed for, since the evaluation is not
+/// zero-sum, so entries are keyed on the side to move and the searching player alike.
+/// Entries are bucketed by a hash of that key and confirmed against the stored board,
+/// so a probe never clones the board.
+#[derive(Debug, Clone, Default)]
+pub struct TranspositionTable {
+    buckets: HashMap<u64, Vec<TableEntry>>,
+    hasher: RandomState,
+    len: usize,
+}
+
+#[derive(Debug, Clone)]
+struct TableEntry {
+    board: ChessBallBoard,
+    to_move: Player,
+    root_player: Player,
+    depth: usize,
+    score: f64,
+}
+
+impl TableEntry {
+    fn is_for(&self, board: &ChessBallBoard, to_move: Player, root_player: Player) -> bool {
+        self.to_move == to_move && self.root_player == root_player && self.board == *board
+    }
+}
+
+impl TranspositionTable {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.buckets.clear();
+        self.len = 0;
+    }
+
+    fn key(&self, board: &ChessBallBoard, to_move: Player, root_player: Player) -> u64 {
+        self.hasher.hash_one((board, to_move, root_player))
+    }
+
+    /// The score of `board`, if it was searched at least `depth` plies deep.
+    fn probe(
+        &self,
+        board: &ChessBallBoard,
+        to_move: Player,
+        root_player: Player,
+        depth: usize,
+    ) -> Option<f64> {
+        let bucket = self.buckets.get(&self.key(board, to_move, root_player))?;
+        let entry = bucket
+            .iter()
+            .find(|entry| entry.is_for(board, to_move, root_player))?;
+        (entry.depth >= depth).then_some(entry.score)
+    }
+
+    fn store(
+        &mut self,
+        board: &ChessBallBoard,
+        to_move: Player,
+        root_player: Player,
+        depth: usize,
+        score: f64,
+    ) {
+        let key = self.key(board, to_move, root_player);
+        let bucket = self.buckets.entry(key).or_default();
+        if let Some(entry) = bucket
+            .iter_mut()
+            .find(|entry| entry.is_for(board, to_move, root_player))
+        {
+            entry.depth = depth;
+            entry.score = score;
+        } else {
+            bucket.push(TableEntry {
+                board: board.clone(),
+                to_move,
+                root_player,
+                depth,
+                score,
+            });
+            self.len += 1;
+        }
+    }
+}
+
+/// A legacy-board search engine: evaluation weights, transposition table, limits and an
+/// optional RNG, used by `best_move`.
+///
+/// Example:
+/// ```
+/// use chessball::board::{ChessBallBoard, Player};
+/// use chessball::heuristics::HeuristicWeights;
+/// use chessball::search_engine::Engine;
+/// let mut engine = Engine::new(HeuristicWeights::DEFAULT).with_seed(7);
+/// let (mv, _, _) = engine.best_move(&ChessBallBoard::new_game(), Player::White);
+/// assert!(mv.is_some());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Engine {
+    weights: HeuristicWeights,
+    pub limits: SearchLimits,
+    table: TranspositionTable,
+    rng: Option<XorShift64>,
+    nodes: u64,
+    deadline: Option<Instant>,
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Self::new(HeuristicWeights::DEFAULT)
+    }
+}
+
+impl Engine {
+    /// An engine evaluating with `weights`, with default limits and no RNG.
+    #[must_use]
+    pub fn new(weights: HeuristicWeights) -> Self {
+        Self {
+            weights,
+            limits: SearchLimits::default(),
+            table: TranspositionTable::new(),
+            rng: None,
+            nodes: 0,
+            deadline: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_limits(mut self, limits: SearchLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Break ties between equally scored moves at random, reproducibly from `seed`.
+    /// Without an RNG the first of them, in `possible_moves` order, is played.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Some(XorShift64::new(seed));
+        self
+    }
+
+    #[must_use]
+    pub fn weights(&self) -> &HeuristicWeights {
+        &self.weights
+    }
+
+    /// Evaluate with `weights` from now on. Stored scores came from the old weights, so
+    /// the transposition table is cleared.
+    pub fn set_weights(&mut self, weights: HeuristicWeights) {
+        self.weights = weights;
+        self.clear();
+    }
+
+    #[must_use]
+    pub fn table(&self) -> &TranspositionTable {
+        &self.table
+    }
+
+    /// Nodes visited by the last `best_move`.
+    #[must_use]
+    pub fn nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    /// Forget every stored position.
+    pub fn clear(&mut self) {
+        self.table.clear();
+    }
+
+    /// Choose a move for `player` on `board`, as (best_move, best_board_after, score).
+    ///
+    /// Searches with iterative deepening up to `limits.depth`, scoring leaves with
+    /// `evaluate_with` and `weights`. When the time or node budget runs out, the result
+    /// of the last completed iteration is returned, or the best move found so far if
+    /// none completed, or the first move at its static evaluation if none was scored. Like `choose_best_move`, a finished game or `Neutral` gives no
+    /// move and an immediate win is played at once.
+    pub fn best_move(
+        &mut self,
+        board: &ChessBallBoard,
+        player: Player,
+    ) -> (Option<MoveInfo>, Option<ChessBallBoard>, f64) {
+        self.nodes = 0;
+        self.deadline = self.limits.time.map(|budget| Instant::now() + budget);
+        if player == Player::Neutral {
+            return (None, None, 0.0);
+        }
+        if let Some(winner) = board.winner() {
+            return (None, None, win_score(winner, player));
+        }
+        if let Some((mv, board_after)) = has_immediate_win(board, player) {
+            return (Some(mv), Some(board_after), f64::INFINITY);
+        }
+        let moves = possible_moves(board, player);
+        if moves.is_empty() {
+            return (None, None, evaluate_with(board, player, &self.weights));
+        }
+
+        let mut best: Option<(Vec<usize>, f64)> = None;
+        for depth in 1..=self.limits.depth.max(1) {
+            let mut iteration: (Vec<usize>, f64) = (Vec::new(), f64::NEG_INFINITY);
+            let mut complete = true;
+            for (i, (_, board_after)) in moves.iter().enumerate() {
+                let Some(score) = self.minimax(board_after, player.opponent(), depth - 1, player)
+                else {
+                    complete = false;
+                    break;
+                };
+                if score > iteration.1 || iteration.0.is_empty() {
+                    iteration = (vec![i], score);
+                } else if score == iteration.1 {
+                    iteration.0.push(i);
+                }
+            }
+            if complete {
+                best = Some(iteration);
+            } else {
+                // Keep the last full iteration; a partial one only beats having nothing.
+                if best.is_none() && !iteration.0.is_empty() {
+                    best = Some(iteration);
+                }
+                break;
+            }
+        }
+
+        // With no root move scored at all, play the first one at its static evaluation
+        // rather than a score that reads as a proven loss.
+        let (tied, score) = best.unwrap_or_else(|| {
+            let score = evaluate_for(&moves[0].1, player, player.opponent(), &self.weights);
+            (vec![0], score)
+        });
+        let pick = match &mut self.rng {
+            Some(rng) => tied[rng.choose_index(tied.len())],
+            None => tied[0],
+        };
+        let (mv, board_after) = moves[pick].clone();
+        (Some(mv), Some(board_after), score)
+    }
+
+    /// Minimax score of `board` for `root_player`, or None when a budget ran out.
+    fn minimax(
+        &mut self,
+        board: &ChessBallBoard,
+        to_move: Player,
+        depth: usize,
+        root_player: Player,
+    ) -> Option<f64> {
+        if self.out_of_budget() {
+            return None;
+        }
+        self.nodes += 1;
+        if let Some(winner) = board.winner() {
+            return Some(win_score(winner, root_player));
+        }
+        if has_immediate_win(board, to_move).is_some() {
+            return Some(win_score(to_move, root_player));
+        }
+        if depth == 0 {
+            return Some(evaluate_for(board, root_player, to_move, &self.weights));
+        }
+        if let Some(score) = self.table.probe(board, to_move, root_player, depth) {
+            return Some(score);
+        }
+        let moves = possible_moves(board, to_move);
+        if moves.is_empty() {
+            return Some(evaluate_for(board, root_player, to_move, &self.weights));
+        }
+        let maximizing = to_move == root_player;
+        let mut best = if maximizing {
+            f64::NEG_INFINITY
+        } else {
+            f64::INFINITY
+        };
+        for (_, board_after) in &moves {
+            let score = self.minimax(board_after, to_move.opponent(), depth - 1, root_player)?;
+            best = if maximizing {
+                best.max(score)
+            } else {
+                best.min(score)
+            };
+        }
+        self.table.store(board, to_move, root_player, depth, best);
+        Some(best)
+    }
+
+    fn out_of_budget(&self) -> bool {
+        if self.limits.nodes.is_some_and(|limit| self.nodes >= limit) {
+            return true;
+        }
+        self.nodes.is_multiple_of(TIME_CHECK_INTERVAL)
+            && self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::{BoardBuilder, ChessBallBoard, Player};
+    use crate::heuristics::{Features, HeuristicWeights, evaluate_for};
+    use crate::minimax::choose_best_move;
+    use crate::moves::possible_moves;
+    use crate::search_engine::{Engine, SearchLimits};
+
+    #[test]
+    fn test_engine_with_custom_weights_returns_a_legal_move() {
+        let mut weights = HeuristicWeights::DEFAULT;
+        weights.features = [0.0; Features::COUNT];
+        let ball_row = Features::NAMES
+            .iter()
+            .position(|&n| n == "ball_row")
+            .unwrap();
+        weights.features[ball_row] = 1.0;
+
+        let board = ChessBallBoard::new_game();
+        let mut engine = Engine::new(weights).with_limits(SearchLimits {
+            depth: 2,
+            ..SearchLimits::default()
+        });
+        let (mv, board_after, score) = engine.best_move(&board, Player::White);
+        let mv = mv.unwrap();
+        assert!(
+            possible_moves(&board, Player::White)
+                .iter()
+                .any(|(legal, after)| *legal == mv && Some(after) == board_after.as_ref())
+        );
+        assert!(score.is_finite());
+        assert!(!engine.table().is_empty());
+
+        // Scores stored under the old weights must not survive a change of weights.
+        engine.set_weights(HeuristicWeights::DEFAULT);
+        assert_eq!(engine.weights(), &HeuristicWeights::DEFAULT);
+        assert!(engine.table().is_empty());
+    }
+
+    #[test]
+    fn test_engine_matches_minimax_with_default_weights() {
+        let board = ChessBallBoard::new_game();
+        let mut engine = Engine::default();
+        assert_eq!(
+            engine.best_move(&board, Player::Black),
+            choose_best_move(&board, Player::Black, 2)
+        );
+    }
+
+    #[test]
+    fn test_engine_plays_immediate_win_and_respects_node_limit() {
+        let board = BoardBuilder::new()
+            .defender(Player::White, 3, 3)
+            .ball(4, 3)
+            .defender(Player::Black, 0, 0)
+            .build()
+            .unwrap();
+        let (_, board_after, score) = Engine::default().best_move(&board, Player::White);
+        assert_eq!(score, f64::INFINITY);
+        assert_eq!(board_after.unwrap().winner(), Some(Player::White));
+
+        let mut limited = Engine::default().with_limits(SearchLimits {
+            depth: 6,
+            nodes: Some(50),
+            ..SearchLimits::default()
+        });
+        let (mv, _, _) = limited.best_move(&ChessBallBoard::new_game(), Player::White);
+        assert!(mv.is_some());
+        assert!(limited.nodes() <= 50);
+
+        // Without a single node to spend, the first move comes back at its static
+        // evaluation, not as a proven loss.
+        let mut starved = Engine::default().with_limits(SearchLimits {
+            nodes: Some(0),
+            ..SearchLimits::default()
+        });
+        let board = ChessBallBoard::new_game();
+        let (mv, board_after, score) = starved.best_move(&board, Player::White);
+        let (first, first_after) = possible_moves(&board, Player::White)[0].clone();
+        assert_eq!(mv, Some(first));
+        assert_eq!(
+            score,
+            evaluate_for(
+                &first_after,
+                Player::White,
+                Player::Black,
+                &HeuristicWeights::DEFAULT
+            )
+        );
+        assert_eq!(board_after, Some(first_after));
+    }
+
+    #[test]
+    fn test_seeded_engines_agree() {
+        let board = ChessBallBoard::new_game();
+        let mut a = Engine::default().with_seed(3);
+        let mut b = Engine::default().with_seed(3);
+        assert_eq!(
+            a.best_move(&board, Player::White),
+            b.best_move(&board, Player::White)
+        );
+    }
+}
@@ -22,6 +22,19 @@ pub struct SearchResult {
     pub nodes: u64,
 }
 
+/// Statistics of an iterative-deepening search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SearchStats {
+    /// Deepest completed iteration.
+    pub depth: u8,
+    /// Nodes searched over all iterations and re-searches.
+    pub nodes: u64,
+    /// Aspiration windows that failed low (score at or below the window).
+    pub fail_lows: u32,
+    /// Aspiration windows that failed high (score at or above the window).
+    pub fail_highs: u32,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Bound {
     Exact,
@@ -37,30 +50,169 @@ struct TableEntry {
     best_move: Option<Move>,
 }
 
-#[derive(Debug, Default)]
+/// Half-width of the aspiration window around the score of the iteration two plies
+/// shallower.
+const ASPIRATION_WINDOW: i32 = 50;
+
+/// Extra depth reduction of the null-move search.
+const NULL_MOVE_REDUCTION: u8 = 2;
+
+/// Moves searched at full depth before late-move reductions kick in.
+const LMR_FULL_DEPTH_MOVES: usize = 3;
+
+/// Smallest remaining depth at which late moves are reduced.
+const LMR_MIN_DEPTH: u8 = 4;
+
+/// Depth reduction of late moves: two plies, so reduced searches end on the same side
+/// to move as full ones and the side-relative evaluation stays comparable.
+const LMR_REDUCTION: u8 = 2;
+
+/// Killer moves remembered per ply.
+const KILLERS_PER_PLY: usize = 2;
+
+#[derive(Debug)]
 pub struct Searcher {
     table: HashMap<Position, TableEntry>,
+    /// Moves that recently caused a beta cutoff, indexed by ply, most recent first.
+    killers: Vec<[Option<Move>; KILLERS_PER_PLY]>,
+    use_killers: bool,
+    /// How often each quiet (player, from, to) move caused a cutoff, weighted by depth.
+    history: HashMap<(Player, Square, Square), u32>,
+    use_history: bool,
+    use_null_move: bool,
+    use_late_move_reductions: bool,
     nodes: u64,
 }
 
+impl Default for Searcher {
+    fn default() -> Self {
+        Self {
+            table: HashMap::new(),
+            killers: Vec::new(),
+            use_killers: true,
+            history: HashMap::new(),
+            use_history: true,
+            use_null_move: false,
+            use_late_move_reductions: false,
+            nodes: 0,
+        }
+    }
+}
+
 impl Searcher {
     #[must_use]
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Enable or disable killer-move ordering (on by default). It only changes the order
+    /// in which moves are searched.
+    #[must_use]
+    pub fn with_killers(mut self, enabled: bool) -> Self {
+        self.use_killers = enabled;
+        self
+    }
+
+    /// Enable or disable the history heuristic (on by default), which orders quiet moves
+    /// by how often they caused cutoffs anywhere in the tree, across searches until
+    /// `clear`. Like killers, it only changes the search order.
+    #[must_use]
+    pub fn with_history(mut self, enabled: bool) -> Self {
+        self.use_history = enabled;
+        self
+    }
+
+    /// Enable or disable null-move pruning (off by default): when passing the turn and
+    /// searching `NULL_MOVE_REDUCTION` plies shallower still fails high, the node is cut
+    /// off. Unlike move ordering this can change results, so it is opt-in.
+    #[must_use]
+    pub fn with_null_move(mut self, enabled: bool) -> Self {
+        self.use_null_move = enabled;
+        self
+    }
+
+    /// Enable or disable late-move reductions (off by default): quiet moves after the
+    /// first `LMR_FULL_DEPTH_MOVES` are searched `LMR_REDUCTION` plies shallower with a
+    /// null window and re-searched at full depth when they fail high. Like null moves,
+    /// this is opt-in.
+    #[must_use]
+    pub fn with_late_move_reductions(mut self, enabled: bool) -> Self {
+        self.use_late_move_reductions = enabled;
+        self
+    }
+
     pub fn clear(&mut self) {
         self.table.clear();
+        self.killers.clear();
+        self.history.clear();
         self.nodes = 0;
     }
 
     #[must_use]
     pub fn best_move(&mut self, position: &Position, depth: u8) -> SearchResult {
         self.nodes = 0;
-        let mut alpha = -INF;
-        let beta = INF;
-        let hash_move = self.table.get(position).and_then(|entry| entry.best_move);
-        let moves = self.ordered_moves(position, hash_move);
+        self.killers.clear();
+        self.search_root(position, depth, -INF, INF)
+    }
+
+    /// Iterative deepening from depth 1 to `max_depth` with aspiration windows: each
+    /// depth is first searched with a window of `ASPIRATION_WINDOW` around the score of
+    /// the iteration two plies shallower, widened to the full range on the side that
+    /// fails. The result matches a full-window `best_move` at `max_depth`; `nodes` covers
+    /// every iteration. A `max_depth` of 0 is searched as depth 1, so a move is still
+    /// chosen.
+    #[must_use]
+    pub fn choose_best_move_id(
+        &mut self,
+        position: &Position,
+        max_depth: u8,
+    ) -> (SearchResult, SearchStats) {
+        self.nodes = 0;
+        self.killers.clear();
+        let mut stats = SearchStats::default();
+        let mut result = self.search_root(position, 1, -INF, INF);
+        let mut scores = vec![result.score];
+        stats.depth = 1;
+        for depth in 2..=max_depth {
+            // The evaluation swings with the side to move at the leaves, so the window is
+            // centred on the score two plies back rather than the previous one.
+            let guess = scores.len().checked_sub(2).map(|i| scores[i]);
+            let (mut alpha, mut beta) = match guess {
+                Some(score) if score.abs() < MATE_SCORE / 2 => {
+                    (score - ASPIRATION_WINDOW, score + ASPIRATION_WINDOW)
+                }
+                _ => (-INF, INF),
+            };
+            loop {
+                result = self.search_root(position, depth, alpha, beta);
+                if result.score <= alpha && alpha > -INF {
+                    stats.fail_lows += 1;
+                    alpha = -INF;
+                } else if result.score >= beta && beta < INF {
+                    stats.fail_highs += 1;
+                    beta = INF;
+                } else {
+                    break;
+                }
+            }
+            scores.push(result.score);
+            stats.depth = depth;
+        }
+        stats.nodes = self.nodes;
+        (result, stats)
+    }
+
+    /// Search the moves at the root within (`alpha`, `beta`), without resetting the node
+    /// count or the killers.
+    fn search_root(
+        &mut self,
+        position: &Position,
+        depth: u8,
+        mut alpha: i32,
+        beta: i32,
+    ) -> SearchResult {
+        let hash_move = self.probe(position).and_then(|entry| entry.best_move);
+        let moves = self.ordered_moves(position, hash_move, 0);
 
         if moves.is_empty() {
             return SearchResult {
@@ -74,12 +226,15 @@ impl Searcher {
         let mut best_score = -INF;
         for mv in moves {
             let child = position.apply(mv);
-            let score = -self.negamax(&child, depth.saturating_sub(1), -beta, -alpha, 1);
+            let score = -self.negamax(&child, depth.saturating_sub(1), -beta, -alpha, 1, true);
             if score > best_score {
                 best_score = score;
                 best_move = Some(mv);
             }
             alpha = alpha.max(score);
+            if alpha >= beta {
+                break;
+            }
         }
 
         SearchResult {
@@ -89,6 +244,8 @@ impl Searcher {
         }
     }
 
+    /// `allow_null` is false right after a null move, so the side to move never passes
+    /// twice in a row.
     fn negamax(
         &mut self,
         position: &Position,
@@ -96,6 +253,7 @@ impl Searcher {
         mut alpha: i32,
         beta: i32,
         ply: i32,
+        allow_null: bool,
     ) -> i32 {
         self.nodes += 1;
         if let Some(winner) = position.winner() {
@@ -112,7 +270,7 @@ impl Searcher {
 
         let alpha_orig = alpha;
         let mut hash_move = None;
-        if let Some(entry) = self.table.get(position).copied()
+        if let Some(entry) = self.probe(position)
             && entry.depth >= depth
         {
             hash_move = entry.best_move;
@@ -126,22 +284,57 @@ impl Searcher {
             }
         }
 
-        let moves = self.ordered_moves(position, hash_move);
+        let moves = self.ordered_moves(position, hash_move, ply);
         if moves.is_empty() {
             return self.evaluate(position);
         }
 
+        if self.use_null_move
+            && allow_null
+            && depth > NULL_MOVE_REDUCTION
+            && Self::null_move_is_safe(position)
+        {
+            let mut passed = *position;
+            passed.to_move = position.to_move.opponent();
+            passed.last_tackle = None;
+            let reduced = depth - 1 - NULL_MOVE_REDUCTION;
+            let score = -self.negamax(&passed, reduced, -beta, -beta + 1, ply + 1, false);
+            if score >= beta {
+                return beta;
+            }
+        }
+
         let mut best_move = None;
         let mut best_score = -INF;
-        for mv in moves {
+        for (index, mv) in moves.into_iter().enumerate() {
             let child = position.apply(mv);
-            let score = -self.negamax(&child, depth - 1, -beta, -alpha, ply + 1);
+            let reduce = self.use_late_move_reductions
+                && index >= LMR_FULL_DEPTH_MOVES
+                && depth >= LMR_MIN_DEPTH
+                && matches!(mv.kind, MoveKind::Simple | MoveKind::Jump { .. });
+            let mut score = -INF;
+            if reduce {
+                // Reduced null-window search; only a fail high needs a full search.
+                score = -self.negamax(
+                    &child,
+                    depth - 1 - LMR_REDUCTION,
+                    -alpha - 1,
+                    -alpha,
+                    ply + 1,
+                    true,
+                );
+            }
+            if !reduce || score > alpha {
+                score = -self.negamax(&child, depth - 1, -beta, -alpha, ply + 1, true);
+            }
             if score > best_score {
                 best_score = score;
                 best_move = Some(mv);
             }
             alpha = alpha.max(score);
             if alpha >= beta {
+                self.remember_killer(ply, mv);
+                self.record_history(position.to_move, mv, depth);
                 break;
             }
         }
@@ -153,8 +346,8 @@ impl Searcher {
         } else {
             Bound::Exact
         };
-        self.table.insert(
-            *position,
+        self.store(
+            position,
             TableEntry {
                 depth,
                 score: best_score,
@@ -165,17 +358,65 @@ impl Searcher {
         best_score
     }
 
-    fn ordered_moves(&self, position: &Position, hash_move: Option<Move>) -> Vec<Move> {
+    /// Whether passing is a fair test of `position`. Near a goal a free move lets the
+    /// opponent push twice in a row, and passing is not a legal move at all, so the null
+    /// move is only tried with the ball at least two rows away from both goals.
+    fn null_move_is_safe(position: &Position) -> bool {
+        let row = position.ball().row();
+        [Player::White, Player::Black]
+            .into_iter()
+            .all(|player| row.abs_diff(player.goal_row()) >= 2)
+    }
+
+    /// Look up `position` in the table. Entries are keyed on the horizontally canonical
+    /// position, so the stored move is mirrored back when `position` is the mirror image.
+    fn probe(&self, position: &Position) -> Option<TableEntry> {
+        let (canonical, symmetry) = position.canonical_horizontal();
+        let entry = self.table.get(&canonical)?;
+        Some(TableEntry {
+            best_move: entry.best_move.map(|mv| mv.apply_symmetry(symmetry)),
+            ..*entry
+        })
+    }
+
+    /// Store `entry` for `position` under its horizontally canonical key.
+    fn store(&mut self, position: &Position, entry: TableEntry) {
+        let (canonical, symmetry) = position.canonical_horizontal();
+        self.table.insert(
+            canonical,
+            TableEntry {
+                best_move: entry.best_move.map(|mv| mv.apply_symmetry(symmetry)),
+                ..entry
+            },
+        );
+    }
+
+    fn ordered_moves(&self, position: &Position, hash_move: Option<Move>, ply: i32) -> Vec<Move> {
+        let killers = self.killers_at(ply);
         let mut moves = position.legal_moves();
-        moves.sort_by_key(|mv| self.move_order_score(position, *mv, hash_move));
+        moves.sort_by_key(|mv| {
+            (
+                self.move_order_score(position, *mv, hash_move, killers),
+                self.history_score(position.to_move, *mv),
+            )
+        });
         moves.reverse();
         moves
     }
 
-    fn move_order_score(&self, position: &Position, mv: Move, hash_move: Option<Move>) -> i32 {
+    fn move_order_score(
+        &self,
+        position: &Position,
+        mv: Move,
+        hash_move: Option<Move>,
+        killers: [Option<Move>; KILLERS_PER_PLY],
+    ) -> i32 {
         if hash_move == Some(mv) {
             return 10_000_000;
         }
+        if let Some(slot) = killers.iter().position(|&killer| killer == Some(mv)) {
+            return 9_000_000 - slot as i32;
+        }
         match mv.kind {
             MoveKind::Push { ball_to } => {
                 let mut score = 500_000 + self.ball_progress(ball_to, position.to_move) * 1_000;
@@ -197,6 +438,52 @@ impl Searcher {
         }
     }
 
+    fn killers_at(&self, ply: i32) -> [Option<Move>; KILLERS_PER_PLY] {
+        if !self.use_killers {
+            return [None; KILLERS_PER_PLY];
+        }
+        self.killers
+            .get(ply as usize)
+            .copied()
+            .unwrap_or([None; KILLERS_PER_PLY])
+    }
+
+    /// Remember `mv` as the latest cutoff move at `ply`, keeping the previous one.
+    fn remember_killer(&mut self, ply: i32, mv: Move) {
+        if !self.use_killers {
+            return;
+        }
+        let ply = ply as usize;
+        if self.killers.len() <= ply {
+            self.killers.resize(ply + 1, [None; KILLERS_PER_PLY]);
+        }
+        let slots = &mut self.killers[ply];
+        if slots[0] != Some(mv) {
+            slots.rotate_right(1);
+            slots[0] = Some(mv);
+        }
+    }
+
+    /// Secondary ordering key: the history of quiet moves, 0 for everything else.
+    fn history_score(&self, player: Player, mv: Move) -> u32 {
+        if !self.use_history || mv.kind != MoveKind::Simple {
+            return 0;
+        }
+        self.history
+            .get(&(player, mv.from, mv.to))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    fn record_history(&mut self, player: Player, mv: Move, depth: u8) {
+        if !self.use_history || mv.kind != MoveKind::Simple {
+            return;
+        }
+        let bonus = u32::from(depth) * u32::from(depth);
+        let entry = self.history.entry((player, mv.from, mv.to)).or_insert(0);
+        *entry = entry.saturating_add(bonus);
+    }
+
     fn evaluate(&self, position: &Position) -> i32 {
         let player = position.to_move;
         let opponent = player.opponent();
@@ -266,7 +553,7 @@ impl Searcher {
 
 #[cfg(test)]
 mod tests {
-    use crate::engine::{Move, MoveKind, Piece, PieceKind, Player, Position, square};
+    use crate::engine::{Move, MoveKind, Piece, PieceKind, Player, Position, Symmetry, square};
 
     use super::Searcher;
 
@@ -304,4 +591,123 @@ mod tests {
         assert!(result.best_move.is_some());
         assert!(result.nodes > 0);
     }
+
+    #[test]
+    fn table_shares_entries_between_mirror_images() {
+        let mut position = Position::new_game();
+        position = position.apply(position.legal_moves()[0]);
+        assert_ne!(position.mirrored_horizontal(), position);
+
+        let mut searcher = Searcher::new();
+        let _ = searcher.best_move(&position, 3);
+        let (child, entry) = position
+            .legal_moves()
+            .into_iter()
+            .map(|mv| position.apply(mv))
+            .filter(|child| child.mirrored_horizontal() != *child)
+            .find_map(|child| Some((child, searcher.probe(&child)?)))
+            .expect("a stored asymmetric child");
+        let stored = entry.best_move.expect("a stored move");
+
+        let mirrored = child.mirrored_horizontal();
+        let mirrored_entry = searcher.probe(&mirrored).expect("mirror shares the entry");
+        assert_eq!(mirrored_entry.score, entry.score);
+        assert_eq!(mirrored_entry.depth, entry.depth);
+        let mirrored_move = mirrored_entry.best_move.unwrap();
+        assert_eq!(
+            mirrored_move,
+            stored.apply_symmetry(Symmetry::MirrorHorizontal)
+        );
+        assert!(child.legal_moves().contains(&stored));
+        assert!(mirrored.legal_moves().contains(&mirrored_move));
+    }
+
+    #[test]
+    fn killers_reduce_nodes_without_changing_result() {
+        // Two plies in, White has ball pushes on offer.
+        let mut position = Position::new_game();
+        for index in [0, 1] {
+            position = position.apply(position.legal_moves()[index]);
+        }
+
+        let with_killers = Searcher::new().best_move(&position, 4);
+        let without = Searcher::new().with_killers(false).best_move(&position, 4);
+        assert_eq!(with_killers.best_move, without.best_move);
+        assert_eq!(with_killers.score, without.score);
+        assert!(with_killers.nodes < without.nodes);
+    }
+
+    #[test]
+    fn history_reduces_nodes_without_changing_result() {
+        let mut position = Position::new_game();
+        for index in [0, 1] {
+            position = position.apply(position.legal_moves()[index]);
+        }
+
+        let with_history = Searcher::new().best_move(&position, 4);
+        let without = Searcher::new().with_history(false).best_move(&position, 4);
+        assert_eq!(with_history.best_move, without.best_move);
+        assert_eq!(with_history.score, without.score);
+        assert!(with_history.nodes < without.nodes);
+    }
+
+    #[test]
+    fn null_move_pruning_keeps_the_chosen_move() {
+        let mut position = Position::new_game();
+        let mut nodes_with = 0;
+        let mut nodes_without = 0;
+        for index in [0, 1, 2, 0, 1, 2] {
+            let with_null = Searcher::new().with_null_move(true).best_move(&position, 4);
+            let without = Searcher::new().best_move(&position, 4);
+            assert_eq!(with_null.best_move, without.best_move);
+            nodes_with += with_null.nodes;
+            nodes_without += without.nodes;
+            position = position.apply(position.legal_moves()[index]);
+        }
+        assert!(nodes_with < nodes_without);
+    }
+
+    #[test]
+    fn late_move_reductions_keep_the_chosen_move() {
+        // Positions after two, three and four plies of the opening.
+        let mut position = Position::new_game();
+        for index in [0, 1] {
+            position = position.apply(position.legal_moves()[index]);
+        }
+        let mut nodes_with = 0;
+        let mut nodes_without = 0;
+        for index in [2, 0, 1] {
+            let with_lmr = Searcher::new()
+                .with_late_move_reductions(true)
+                .best_move(&position, 5);
+            let without = Searcher::new().best_move(&position, 5);
+            assert_eq!(with_lmr.best_move, without.best_move);
+            nodes_with += with_lmr.nodes;
+            nodes_without += without.nodes;
+            position = position.apply(position.legal_moves()[index]);
+        }
+        assert!(nodes_with < nodes_without);
+    }
+
+    #[test]
+    fn aspiration_windows_match_full_window_search() {
+        let mut position = Position::new_game();
+        let mut failures = 0;
+        for index in [0, 1, 2] {
+            let (deepened, stats) = Searcher::new().choose_best_move_id(&position, 5);
+            let full = Searcher::new().best_move(&position, 5);
+            assert_eq!(deepened.best_move, full.best_move);
+            assert_eq!(deepened.score, full.score);
+            assert_eq!(stats.depth, 5);
+            assert_eq!(stats.nodes, deepened.nodes);
+            failures += stats.fail_lows + stats.fail_highs;
+            position = position.apply(position.legal_moves()[index]);
+        }
+        // Some windows fail, so the widening path is exercised too.
+        assert!(failures > 0);
+
+        let (shallow, stats) = Searcher::new().choose_best_move_id(&Position::new_game(), 0);
+        assert_eq!(stats.depth, 1);
+        assert!(shallow.best_move.is_some());
+    }
 }
@@ -1,8 +1,12 @@
 use std::collections::HashMap;
 
 use crate::{
+    board,
     engine::{Player, Position},
+    game::{GameResult, GameState},
+    heuristics::HeuristicWeights,
     record::{GameOutcome, GameRecord, MoveSource, PlyRecord, Termination},
+    search_engine::{Engine, SearchLimits},
     solver::Searcher,
 };
 
@@ -47,6 +51,17 @@ pub struct TournamentStats {
 }
 
 impl TournamentStats {
+    /// Count one finished game of `plies` plies.
+    fn record(&mut self, outcome: GameOutcome, plies: usize) {
+        self.games += 1;
+        self.total_plies += plies;
+        match outcome {
+            GameOutcome::WhiteWin => self.white_wins += 1,
+            GameOutcome::BlackWin => self.black_wins += 1,
+            GameOutcome::Draw => self.draws += 1,
+        }
+    }
+
     #[must_use]
     pub fn average_plies(&self) -> f64 {
         if self.games == 0 {
@@ -224,14 +239,8 @@ pub fn run_selfplay_tournament(config: TournamentConfig) -> (TournamentReport, V
             plies: record.moves.len(),
             total_nodes,
         };
-        stats.games += 1;
-        stats.total_plies += summary.plies;
+        stats.record(summary.outcome, summary.plies);
         stats.total_nodes += total_nodes;
-        match summary.outcome {
-            GameOutcome::WhiteWin => stats.white_wins += 1,
-            GameOutcome::BlackWin => stats.black_wins += 1,
-            GameOutcome::Draw => stats.draws += 1,
-        }
         summaries.push(summary);
         records.push(record);
     }
@@ -246,6 +255,80 @@ pub fn run_selfplay_tournament(config: TournamentConfig) -> (TournamentReport, V
     )
 }
 
+/// Play every pair of weight sets against each other, `games` games per pair.
+///
+/// Returns one `(i, j, score)` per pair with `i < j`, where `score` is `configs[i]`'s
+/// average result against `configs[j]`: +1 per win, -1 per loss and 0 per draw. Games
+/// are played on the legacy board by `GameState::auto_play_with` with the default
+/// `TournamentConfig` ply limit, each side searching `depth` plies with an `Engine` on
+/// its own weights, and `configs[i]` takes White in even-numbered games and Black in
+/// odd ones.
+#[must_use]
+pub fn weights_tournament(
+    configs: &[HeuristicWeights],
+    depth: usize,
+    games: usize,
+) -> Vec<(usize, usize, f64)> {
+    let max_plies = TournamentConfig::default().max_plies;
+    let mut results = Vec::new();
+    for i in 0..configs.len() {
+        for j in i + 1..configs.len() {
+            let mut i_white = TournamentStats::default();
+            let mut i_black = TournamentStats::default();
+            for game in 0..games {
+                if game % 2 == 0 {
+                    let (outcome, plies) =
+                        play_weights_game(&configs[i], &configs[j], depth, max_plies);
+                    i_white.record(outcome, plies);
+                } else {
+                    let (outcome, plies) =
+                        play_weights_game(&configs[j], &configs[i], depth, max_plies);
+                    i_black.record(outcome, plies);
+                }
+            }
+            let wins = i_white.white_wins + i_black.black_wins;
+            let losses = i_white.black_wins + i_black.white_wins;
+            let score = if games == 0 {
+                0.0
+            } else {
+                (wins as f64 - losses as f64) / games as f64
+            };
+            results.push((i, j, score));
+        }
+    }
+    results
+}
+
+/// Play one legacy-board game between two weight sets and report its outcome and length.
+fn play_weights_game(
+    white: &HeuristicWeights,
+    black: &HeuristicWeights,
+    depth: usize,
+    max_plies: usize,
+) -> (GameOutcome, usize) {
+    let limits = SearchLimits {
+        depth,
+        ..SearchLimits::default()
+    };
+    let mut white_engine = Engine::new(*white).with_limits(limits);
+    let mut black_engine = Engine::new(*black).with_limits(limits);
+    let mut game = GameState::new_game();
+    let result = game.auto_play_with(
+        max_plies,
+        |board, player| match player {
+            board::Player::White => white_engine.best_move(board, player),
+            _ => black_engine.best_move(board, player),
+        },
+        |_, _| {},
+    );
+    let outcome = match result {
+        GameResult::Win(board::Player::White) => GameOutcome::WhiteWin,
+        GameResult::Win(_) => GameOutcome::BlackWin,
+        GameResult::Draw(_) => GameOutcome::Draw,
+    };
+    (outcome, game.moves().count())
+}
+
 #[must_use]
 fn winner_to_outcome(winner: Player) -> GameOutcome {
     match winner {
@@ -289,7 +372,9 @@ impl XorShift64 {
 mod tests {
     use super::{
         GameOutcome, Termination, TournamentConfig, play_selfplay_game, run_selfplay_tournament,
+        weights_tournament,
     };
+    use crate::heuristics::{Features, HeuristicWeights};
 
     #[test]
     fn selfplay_game_produces_replayable_record() {
@@ -326,10 +411,22 @@ mod tests {
                 | Termination::ThreefoldRepetition
                 | Termination::PlyLimit
                 | Termination::NoLegalMoves
+                | Termination::InsufficientMaterial
         ));
         assert!(matches!(
             record.outcome,
             GameOutcome::WhiteWin | GameOutcome::BlackWin | GameOutcome::Draw
         ));
     }
+
+    #[test]
+    fn weights_tournament_result_shape() {
+        let mut flat = HeuristicWeights::DEFAULT;
+        flat.features = [0.0; Features::COUNT];
+        let results = weights_tournament(&[HeuristicWeights::DEFAULT, flat], 1, 2);
+        assert_eq!(results.len(), 1);
+        let (i, j, score) = results[0];
+        assert_eq!((i, j), (0, 1));
+        assert!((-1.0..=1.0).contains(&score));
+    }
 }
@@ -0,0 +1,37 @@
+//! WebAssembly bindings for browser play (enabled with the `wasm` feature).
+//!
+//! Boards are passed in the textual format understood by `ChessBallBoard::from_repr`
+//! and players as their single-letter initial (`"W"` or `"B"`). Results are JSON
+//! strings produced by the `json` module.
+
+use wasm_bindgen::prelude::*;
+
+use crate::board::{ChessBallBoard, Player};
+use crate::json::{best_move_json, board_json, legal_moves_json};
+
+/// Parse a board and return it as JSON.
+#[wasm_bindgen]
+pub fn parse_board(repr: &str) -> Result<String, JsValue> {
+    Ok(board_json(&parse(repr)?))
+}
+
+/// Return the legal moves of `player` on the given board as a JSON array.
+#[wasm_bindgen]
+pub fn legal_moves(repr: &str, player: &str) -> Result<String, JsValue> {
+    Ok(legal_moves_json(&parse(repr)?, parse_player(player)?))
+}
+
+/// Search `depth` plies and return the chosen move and resulting board as JSON.
+#[wasm_bindgen]
+pub fn best_move(repr: &str, player: &str, depth: usize) -> Result<String, JsValue> {
+    Ok(best_move_json(&parse(repr)?, parse_player(player)?, depth))
+}
+
+fn parse(repr: &str) -> Result<ChessBallBoard, JsValue> {
+    ChessBallBoard::from_repr(repr).map_err(|err| JsValue::from_str(&err))
+}
+
+fn parse_player(player: &str) -> Result<Player, JsValue> {
+    Player::from_initial(player)
+        .ok_or_else(|| JsValue::from_str(&format!("invalid player '{player}'")))
+}
@@ -164,17 +164,16 @@ impl ProgressReporter {
         };
 
         let mut should_report = false;
-        if let Some(interval) = self.checkpoint_states {
-            if let Some(next) = phase.next_state_checkpoint
-                && processed >= next
-            {
-                should_report = true;
-                let mut updated = next;
-                while processed >= updated {
-                    updated = updated.saturating_add(interval);
-                }
-                phase.next_state_checkpoint = Some(updated);
+        if let Some(interval) = self.checkpoint_states
+            && let Some(next) = phase.next_state_checkpoint
+            && processed >= next
+        {
+            should_report = true;
+            let mut updated = next;
+            while processed >= updated {
+                updated = updated.saturating_add(interval);
             }
+            phase.next_state_checkpoint = Some(updated);
         }
 
         if !should_report
@@ -606,7 +605,7 @@ impl StateIdTable {
         self.len = 0;
         self.peak_bytes = self.peak_bytes.max(self.bytes());
 
-        for (key, value) in old_keys.into_iter().zip(old_values.into_iter()) {
+        for (key, value) in old_keys.into_iter().zip(old_values) {
             if key != Self::EMPTY {
                 self.insert_raw(key, value);
             }
@@ -1027,7 +1026,7 @@ fn build_graph(
     let (start_position, start_symmetry) = start.canonical_color_preserving();
     let start_key = start_position.pack();
 
-    let reserve = config.max_states.unwrap_or(1024).min(131_072).max(16);
+    let reserve = config.max_states.unwrap_or(1024).clamp(16, 131_072);
     let mut state_keys = Vec::with_capacity(reserve);
     let mut to_move = Vec::with_capacity(reserve);
     let mut winners = Vec::with_capacity(reserve);
@@ -1173,7 +1172,7 @@ fn build_graph(
         state_keys.len(),
         cursor,
         state_table_peak_bytes,
-        reporter.as_deref_mut(),
+        reporter,
     );
 
     StateGraph {
@@ -1287,7 +1286,7 @@ fn build_predecessors(
             });
         }
     }
-    if let Some(reporter) = reporter.as_deref_mut() {
+    if let Some(reporter) = reporter {
         reporter.finish_phase(expanded_states, || ProgressSnapshot {
             states: num_states,
             expanded_states,
@@ -1377,7 +1376,7 @@ fn retrograde_outcomes(
         }
     }
 
-    if let Some(reporter) = reporter.as_deref_mut() {
+    if let Some(reporter) = reporter {
         reporter.finish_phase(processed, || ProgressSnapshot {
             states: graph.state_keys.len(),
             expanded_states: graph.expanded_states,
@@ -1694,15 +1693,13 @@ fn compute_stats(
         }
     }
 
-    if truncated {
-        if let Some(certified_outcomes) = certified_outcomes {
-            for outcome in certified_outcomes {
-                match outcome {
-                    Some(Outcome::WhiteWin) => stats.certified_white_wins += 1,
-                    Some(Outcome::BlackWin) => stats.certified_black_wins += 1,
-                    Some(Outcome::Draw) => stats.certified_draws += 1,
-                    None => {}
-                }
+    if truncated && let Some(certified_outcomes) = certified_outcomes {
+        for outcome in certified_outcomes {
+            match outcome {
+                Some(Outcome::WhiteWin) => stats.certified_white_wins += 1,
+                Some(Outcome::BlackWin) => stats.certified_black_wins += 1,
+                Some(Outcome::Draw) => stats.certified_draws += 1,
+                None => {}
             }
         }
     }
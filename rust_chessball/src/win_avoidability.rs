@@ -1,5 +1,7 @@
 //! Check whether a win was avoidable by the opponent by examining possible previous positions.
 
+use std::collections::HashSet;
+
 use crate::blocking_move::find_blocking_move;
 use crate::board::{ChessBallBoard, Player};
 use crate::moves::possible_previous_moves;
@@ -9,21 +11,59 @@ use crate::moves::possible_previous_moves;
 ///
 /// Returns `true` if the opponent could have always blocked (i.e., the win was avoidable),
 /// and `false` if there exists a previous position that made the win inevitable.
+/// Only `reachable_predecessors` count, each checked once.
 pub fn is_win_avoidable_by_opponent(position: &ChessBallBoard, player: Player) -> bool {
-    let opponent = match player {
-        Player::White => Player::Black,
-        Player::Black => Player::White,
-        Player::Neutral => Player::Neutral,
-    };
-    let previous_positions = possible_previous_moves(position, opponent);
+    let opponent = player.opponent();
+    let previous_positions = reachable_predecessors(position, opponent);
     if previous_positions.is_empty() {
         return false;
     }
-    for (_mv, prev_board) in previous_positions {
-        let blocking = find_blocking_move(&prev_board, opponent);
-        if blocking.is_none() {
-            return false;
-        }
+    previous_positions
+        .iter()
+        .all(|prev_board| find_blocking_move(prev_board, opponent).is_some())
+}
+
+/// The distinct boards `opponent` could have moved from to reach `position`, leaving out
+/// those no game can pass through: a finished game, or the ball on a forbidden column,
+/// which no push ever moves it into.
+fn reachable_predecessors(position: &ChessBallBoard, opponent: Player) -> Vec<ChessBallBoard> {
+    let mut seen = HashSet::new();
+    possible_previous_moves(position, opponent)
+        .into_iter()
+        .map(|(_mv, prev_board)| prev_board)
+        .filter(|prev_board| {
+            prev_board.winner().is_none()
+                && prev_board
+                    .find_ball()
+                    .is_none_or(|ball| !prev_board.is_forbidden_col(ball))
+        })
+        .filter(|prev_board| seen.insert(prev_board.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::{BoardBuilder, Player};
+    use crate::win_avoidability::is_win_avoidable_by_opponent;
+
+    #[test]
+    fn test_ball_on_forbidden_column_is_not_a_predecessor() {
+        // With column 2 forbidden the ball cannot have come from (4, 2), so Black's push
+        // from (4, 1), after which no block exists, is not a real predecessor. From
+        // every other one Black could have stopped the push onto row 5.
+        let board = BoardBuilder::new()
+            .forbidden_cols(&[0, 2, 6])
+            .defender(Player::White, 3, 1)
+            .defender(Player::White, 3, 3)
+            .attacker(Player::Black, 4, 2)
+            .ball(4, 3)
+            .defender(Player::Black, 4, 4)
+            .build()
+            .unwrap();
+        assert!(is_win_avoidable_by_opponent(&board, Player::White));
+
+        let mut standard = board.clone();
+        standard.set_forbidden_cols([0, 6]);
+        assert!(!is_win_avoidable_by_opponent(&standard, Player::White));
     }
-    true
 }
@@ -2,24 +2,152 @@
 
 use crate::board::ChessBallBoard;
 use crate::board::Player;
-use crate::moves::{MoveInfo, possible_moves};
+use crate::moves::{MoveInfo, MoveSpecialInfo, apply_move, move_list};
 
 /// Return the list of moves for `player` that result in the ball being in the player's goal row.
 ///
-/// For Black the winning row is 0; for White it's rows-1.
+/// For Black the winning row is 0; for White it's rows-1. `Neutral` has no goal row and
+/// never wins.
+///
+/// Own goals are legal, as in the official rules (`CHESSBALL_RULES_OFFICIAL_SPEC.md`,
+/// section 8): the goal row the ball reaches decides the winner, whoever pushed it there.
+/// A push onto `player`'s own goal row is therefore not listed here; it wins the game for
+/// the opponent (see `own_goal_moves`).
+///
+/// The order is fixed, independent of move generation: by the square the ball ends on
+/// (row, then column), then by the moving piece's source square, then by its target.
+/// `minimax::has_immediate_win`, which plays the first move, is therefore deterministic.
 pub fn winning_moves(position: &ChessBallBoard, player: Player) -> Vec<MoveInfo> {
-    let winner_row = match player {
-        Player::Black => 0usize,
-        Player::White => position.rows - 1,
-        Player::Neutral => position.rows - 1, // neutral not used
+    winning_moves_in(position, player, &move_list(position, player))
+}
+
+/// The moves in `moves`, a move list of `player` on `position`, that win as in
+/// `winning_moves`. Lets callers that already hold the move list skip generating it again.
+pub(crate) fn winning_moves_in(
+    position: &ChessBallBoard,
+    player: Player,
+    moves: &[MoveInfo],
+) -> Vec<MoveInfo> {
+    let Some(winner_row) = player.goal_row(position.rows) else {
+        return Vec::new();
     };
-    let mut wins = Vec::new();
-    for (mv, board_after) in possible_moves(position, player) {
-        if let Some(ball_coord) = board_after.find_ball()
-            && ball_coord.r == winner_row
-        {
-            wins.push(mv);
-        }
-    }
+    // Only a ball push moves the ball, so no resulting board is needed.
+    let ball = position.find_ball();
+    let ball_on_goal_row = ball.is_some_and(|ball| ball.r == winner_row);
+    let mut wins: Vec<MoveInfo> = moves
+        .iter()
+        .filter(|mv| match mv.special {
+            MoveSpecialInfo::BallPush { ball_to } => ball_to.r == winner_row,
+            _ => ball_on_goal_row,
+        })
+        .cloned()
+        .collect();
+    wins.sort_by_key(|mv| {
+        let ball_after = match mv.special {
+            MoveSpecialInfo::BallPush { ball_to } => Some(ball_to),
+            _ => ball,
+        };
+        (ball_after, mv.from, mv.to)
+    });
     wins
 }
+
+/// Return the moves for `player` that push the ball onto the opponent's goal row, the row
+/// `player` defends. They stay legal and hand the opponent the win: afterwards
+/// `ChessBallBoard::winner` is the opponent.
+pub fn own_goal_moves(position: &ChessBallBoard, player: Player) -> Vec<MoveInfo> {
+    let Some(own_goal_row) = player.opponent().goal_row(position.rows) else {
+        return Vec::new();
+    };
+    move_list(position, player)
+        .into_iter()
+        .filter(|mv| {
+            matches!(mv.special, MoveSpecialInfo::BallPush { ball_to } if ball_to.r == own_goal_row)
+        })
+        .collect()
+}
+
+/// Return the moves for `player` that set up a win: afterwards `player` would have a
+/// winning move if it were their turn again. Moves that win outright are not included.
+pub fn threatening_moves(position: &ChessBallBoard, player: Player) -> Vec<MoveInfo> {
+    let wins = winning_moves(position, player);
+    move_list(position, player)
+        .into_iter()
+        .filter(|mv| !wins.contains(mv))
+        .filter(|mv| !winning_moves(&apply_move(position, mv), player).is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::{BoardBuilder, Coord, Player};
+    use crate::moves::MoveInfo;
+    use crate::moves::{apply_move, possible_moves};
+    use crate::winning_moves::{own_goal_moves, threatening_moves, winning_moves};
+
+    #[test]
+    fn test_threatening_moves_finds_the_set_up() {
+        // Only stepping in front of the ball sets up a push onto row 5: the diagonal
+        // pushes from (3,2) and (3,4) are blocked by Black's pieces.
+        let board = BoardBuilder::new()
+            .defender(Player::White, 2, 3)
+            .ball(4, 3)
+            .defender(Player::Black, 5, 2)
+            .defender(Player::Black, 5, 4)
+            .build()
+            .unwrap();
+        assert!(winning_moves(&board, Player::White).is_empty());
+        assert_eq!(
+            threatening_moves(&board, Player::White),
+            vec![MoveInfo::simple(Coord { r: 2, c: 3 }, Coord { r: 3, c: 3 })]
+        );
+    }
+
+    #[test]
+    fn test_own_goal_counts_for_the_opponent() {
+        // White's defender can push the ball onto row 0, White's own goal row.
+        let board = BoardBuilder::new()
+            .defender(Player::White, 2, 3)
+            .ball(1, 3)
+            .defender(Player::Black, 5, 0)
+            .build()
+            .unwrap();
+        let own_goal = MoveInfo::ball_push(
+            Coord { r: 2, c: 3 },
+            Coord { r: 1, c: 3 },
+            Coord { r: 0, c: 3 },
+        );
+        assert!(
+            possible_moves(&board, Player::White)
+                .iter()
+                .any(|(mv, _)| *mv == own_goal)
+        );
+        assert_eq!(
+            own_goal_moves(&board, Player::White),
+            vec![own_goal.clone()]
+        );
+        assert!(!winning_moves(&board, Player::White).contains(&own_goal));
+        assert!(own_goal_moves(&board, Player::Black).is_empty());
+        assert_eq!(apply_move(&board, &own_goal).winner(), Some(Player::Black));
+    }
+
+    #[test]
+    fn test_winning_moves_order_is_pinned() {
+        // Three defenders can each push the ball onto a different square of row 5.
+        let board = BoardBuilder::new()
+            .defender(Player::White, 3, 2)
+            .defender(Player::White, 3, 3)
+            .defender(Player::White, 3, 4)
+            .ball(4, 3)
+            .build()
+            .unwrap();
+        let ball = Coord { r: 4, c: 3 };
+        let push = |from_c, goal_c| {
+            MoveInfo::ball_push(Coord { r: 3, c: from_c }, ball, Coord { r: 5, c: goal_c })
+        };
+        assert_eq!(
+            winning_moves(&board, Player::White),
+            vec![push(4, 2), push(3, 3), push(2, 4)]
+        );
+    }
+}
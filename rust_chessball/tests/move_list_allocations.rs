@@ -0,0 +1,40 @@
+//! Allocation count of `possible_moves` with the `smallvec` feature.
+//!
+//! Run with `cargo test --features smallvec --test move_list_allocations`.
+#![cfg(feature = "smallvec")]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use chessball::board::{ChessBallBoard, Player};
+use chessball::moves::possible_moves;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+#[test]
+fn possible_moves_only_allocates_the_resulting_boards() {
+    let board = ChessBallBoard::new_game();
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let moves = possible_moves(&board, Player::White);
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+
+    // One allocation per cloned board; a `Vec` move list would add its own growth
+    // reallocations on top.
+    assert_eq!(allocations, moves.len());
+}